@@ -0,0 +1,412 @@
+//! Generates packet structs for `minecraft_protocol` from minecraft-data
+//! (PrismarineJS) `protocol.json` files.
+//!
+//! Fields map onto the crate's `Serialize` / `Deserialize` types: primitives,
+//! strings, `option`, varint-counted `array` and `buffer`, `restBuffer` and
+//! anonymous NBT. Packets using a type from [`SKIPPED_TYPES`] are skipped and
+//! listed in a comment; any other type fails with
+//! [`CodegenError::UnsupportedType`], so nothing is dropped silently.
+
+use std::fmt::Write;
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CodegenError {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Missing or invalid `{0}`")]
+    Missing(String),
+    #[error("Invalid packet ID `{0}`")]
+    InvalidPacketId(String),
+    #[error("Unsupported type `{ty}` in {path}")]
+    UnsupportedType { path: String, ty: String },
+}
+
+/// Types without a Rust counterpart yet. Packets that use one, directly or
+/// through a named type, are skipped.
+pub const SKIPPED_TYPES: &[&str] = &[
+    "bitfield",
+    "bitflags",
+    "container",
+    "entityMetadataLoop",
+    "mapper",
+    "nbt",
+    "optionalNbt",
+    "registryEntryHolder",
+    "registryEntryHolderSet",
+    "switch",
+    "topBitSetTerminatedArray",
+];
+
+/// States in the order they appear in a connection.
+const STATES: &[&str] = &["handshaking", "status", "login", "configuration", "play"];
+
+const DIRECTIONS: &[(&str, &str)] = &[("toServer", "serverbound"), ("toClient", "clientbound")];
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod",
+    "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true",
+    "try", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Converts the contents of a `protocol.json` file into a Rust module.
+///
+/// `source` is only used in the generated header comment.
+pub fn generate(json: &str, source: &str) -> Result<String, CodegenError> {
+    let protocol: Value = serde_json::from_str(json)?;
+    let protocol = protocol
+        .as_object()
+        .ok_or_else(|| CodegenError::Missing("root object".to_string()))?;
+
+    let types = protocol
+        .get("types")
+        .and_then(Value::as_object)
+        .ok_or_else(|| CodegenError::Missing("types".to_string()))?;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// @generated by minecraft_protocol_codegen from {source}. Do not edit."
+    )
+    .unwrap();
+
+    for state in STATES {
+        let Some(state_value) = protocol.get(*state) else {
+            continue;
+        };
+
+        writeln!(out, "\npub mod {state} {{").unwrap();
+        let mut first = true;
+        for (key, direction) in DIRECTIONS {
+            let path = format!("{state}.{key}.types");
+            let state_types = state_value
+                .get(key)
+                .and_then(|d| d.get("types"))
+                .and_then(Value::as_object)
+                .ok_or_else(|| CodegenError::Missing(path.clone()))?;
+
+            if !first {
+                out.push('\n');
+            }
+            first = false;
+            generate_direction(&mut out, direction, types, state_types, &path)?;
+        }
+        writeln!(out, "}}").unwrap();
+    }
+
+    Ok(out)
+}
+
+struct GeneratedField {
+    name: String,
+    ty: String,
+}
+
+/// Why a type has no Rust counterpart.
+enum Unsupported {
+    /// One of [`SKIPPED_TYPES`], or a named type built on one.
+    Skipped(String),
+    Unknown(String),
+}
+
+fn generate_direction(
+    out: &mut String,
+    direction: &str,
+    types: &Map<String, Value>,
+    state_types: &Map<String, Value>,
+    path: &str,
+) -> Result<(), CodegenError> {
+    let mut packets = packet_mappings(state_types, path)?;
+    packets.sort_by_key(|(id, _)| *id);
+
+    let mut structs = String::new();
+    let mut skipped = Vec::new();
+    let mut imports = Vec::new();
+
+    for (id, name) in &packets {
+        let packet_path = format!("{path}.packet_{name}");
+        let fields = state_types
+            .get(&format!("packet_{name}"))
+            .and_then(container_fields)
+            .ok_or_else(|| CodegenError::Missing(packet_path.clone()))?;
+
+        let generated: Result<Vec<GeneratedField>, Unsupported> = fields
+            .iter()
+            .map(|field| {
+                let ty = field
+                    .get("type")
+                    .ok_or_else(|| Unsupported::Unknown(field.to_string()))?;
+                let ty = rust_type(types, ty)?;
+                // Anonymous fields merge a container or switch into the packet
+                let name = field
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| Unsupported::Unknown(format!("anonymous {ty}")))?;
+                Ok(GeneratedField {
+                    name: field_name(name),
+                    ty,
+                })
+            })
+            .collect();
+
+        let generated = match generated {
+            Ok(generated) => generated,
+            Err(Unsupported::Skipped(ty)) => {
+                skipped.push(format!("0x{id:02X} {name} ({ty})"));
+                continue;
+            }
+            Err(Unsupported::Unknown(ty)) => {
+                return Err(CodegenError::UnsupportedType {
+                    path: packet_path,
+                    ty,
+                });
+            }
+        };
+
+        for (ty, import) in IMPORTS {
+            if !imports.contains(import) && generated.iter().any(|f| contains_type(&f.ty, ty)) {
+                imports.push(*import);
+            }
+        }
+
+        writeln!(structs).unwrap();
+        writeln!(
+            structs,
+            "        #[derive(Packet, Debug, Clone, PartialEq)]"
+        )
+        .unwrap();
+        writeln!(structs, "        #[packet(0x{id:02X})]").unwrap();
+        if generated.is_empty() {
+            writeln!(structs, "        pub struct {};", struct_name(name)).unwrap();
+        } else {
+            writeln!(structs, "        pub struct {} {{", struct_name(name)).unwrap();
+            for field in &generated {
+                writeln!(structs, "            pub {}: {},", field.name, field.ty).unwrap();
+            }
+            writeln!(structs, "        }}").unwrap();
+        }
+    }
+
+    if structs.is_empty() && skipped.is_empty() {
+        writeln!(out, "    pub mod {direction} {{}}").unwrap();
+        return Ok(());
+    }
+
+    writeln!(out, "    pub mod {direction} {{").unwrap();
+    if !skipped.is_empty() {
+        writeln!(out, "        // Skipped, unsupported field types:").unwrap();
+        for packet in &skipped {
+            writeln!(out, "        // - {packet}").unwrap();
+        }
+    }
+    if !structs.is_empty() {
+        if !skipped.is_empty() {
+            out.push('\n');
+        }
+        writeln!(out, "        use crate::{};", use_tree(&imports)).unwrap();
+        out.push_str(&structs);
+    }
+    writeln!(out, "    }}").unwrap();
+
+    Ok(())
+}
+
+/// Reads the `packet` mapper, returning `(id, name)` pairs.
+fn packet_mappings(
+    state_types: &Map<String, Value>,
+    path: &str,
+) -> Result<Vec<(i32, String)>, CodegenError> {
+    let missing = || CodegenError::Missing(format!("{path}.packet"));
+
+    let fields = state_types
+        .get("packet")
+        .and_then(container_fields)
+        .ok_or_else(missing)?;
+
+    let mappings = fields
+        .iter()
+        .find(|f| f.get("name").and_then(Value::as_str) == Some("name"))
+        .and_then(|f| f.get("type"))
+        .and_then(|t| t.get(1))
+        .and_then(|m| m.get("mappings"))
+        .and_then(Value::as_object)
+        .ok_or_else(missing)?;
+
+    mappings
+        .iter()
+        .map(|(id, name)| {
+            let parsed = id
+                .strip_prefix("0x")
+                .and_then(|hex| i32::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| CodegenError::InvalidPacketId(id.clone()))?;
+            let name = name
+                .as_str()
+                .ok_or_else(|| CodegenError::Missing(format!("{path}.packet mapping {id}")))?;
+            Ok((parsed, name.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the field list of a `["container", [...]]` type.
+fn container_fields(value: &Value) -> Option<&Vec<Value>> {
+    let array = value.as_array()?;
+    if array.first()?.as_str()? != "container" {
+        return None;
+    }
+    array.get(1)?.as_array()
+}
+
+/// Crate paths the generated code may need, in the order rustfmt sorts
+/// them, keyed by the Rust type that needs them.
+const IMPORTS: &[(&str, &str)] = &[
+    ("Nbt", "nbt::Nbt"),
+    ("PrefixedArray", "ser::PrefixedArray"),
+    ("RestBuffer", "ser::RestBuffer"),
+    ("VarInt", "varint::VarInt"),
+    ("VarLong", "varint::VarLong"),
+];
+
+/// `Packet` plus `imports`, formatted like rustfmt, e.g.
+/// `{Packet, ser::{PrefixedArray, RestBuffer}}`.
+fn use_tree(imports: &[&str]) -> String {
+    let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+    for (_, import) in IMPORTS.iter().filter(|(_, i)| imports.contains(i)) {
+        let (module, name) = import.split_once("::").unwrap();
+        match groups.iter_mut().find(|(m, _)| *m == module) {
+            Some((_, names)) => names.push(name),
+            None => groups.push((module, vec![name])),
+        }
+    }
+
+    if groups.is_empty() {
+        return "Packet".to_string();
+    }
+    let mut paths = vec!["Packet".to_string()];
+    for (module, names) in groups {
+        match names.as_slice() {
+            [name] => paths.push(format!("{module}::{name}")),
+            names => paths.push(format!("{module}::{{{}}}", names.join(", "))),
+        }
+    }
+    format!("{{{}}}", paths.join(", "))
+}
+
+/// Whether `ty` mentions the type `name`, e.g. `Option<VarInt>` and `VarInt`.
+fn contains_type(ty: &str, name: &str) -> bool {
+    ty.split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|part| part == name)
+}
+
+/// Maps a minecraft-data type to a Rust type.
+fn rust_type(types: &Map<String, Value>, ty: &Value) -> Result<String, Unsupported> {
+    let unknown = || Unsupported::Unknown(ty.to_string());
+
+    match ty {
+        Value::String(name) => {
+            let native = match name.as_str() {
+                // Zero means none, but the wire format is a plain varint
+                "varint" | "optvarint" => "VarInt",
+                "varlong" => "VarLong",
+                "bool" => "bool",
+                "i8" => "i8",
+                "i16" => "i16",
+                "i32" => "i32",
+                "i64" => "i64",
+                "u8" => "u8",
+                "u16" => "u16",
+                "u32" => "u32",
+                "u64" => "u64",
+                "f32" => "f32",
+                "f64" => "f64",
+                "UUID" => "u128",
+                "restBuffer" => "RestBuffer",
+                // Network NBT without a root name, TAG_End when absent
+                "anonymousNbt" | "anonOptionalNbt" => "Nbt",
+                skipped if SKIPPED_TYPES.contains(&skipped) => {
+                    return Err(Unsupported::Skipped(skipped.to_string()));
+                }
+                // Aliases such as "string": ["pstring", {"countType": "varint"}]
+                other => {
+                    return match types.get(other) {
+                        Some(Value::String(native)) if native == "native" => {
+                            Err(Unsupported::Unknown(other.to_string()))
+                        }
+                        Some(alias) => rust_type(types, alias).map_err(|e| match e {
+                            Unsupported::Skipped(_) => Unsupported::Skipped(other.to_string()),
+                            unknown => unknown,
+                        }),
+                        None => Err(Unsupported::Unknown(other.to_string())),
+                    };
+                }
+            };
+            Ok(native.to_string())
+        }
+        Value::Array(array) => {
+            let kind = array.first().and_then(Value::as_str).ok_or_else(unknown)?;
+            let options = array.get(1).ok_or_else(unknown)?;
+            let count_type = options.get("countType").and_then(Value::as_str);
+            let count = options.get("count").and_then(Value::as_u64);
+
+            match (kind, count_type, count) {
+                ("pstring", Some("varint"), _) => Ok("String".to_string()),
+                ("buffer", Some("varint"), _) => Ok("Vec<u8>".to_string()),
+                ("buffer", _, Some(count)) => Ok(format!("[u8; {count}]")),
+                ("option", ..) => Ok(format!("Option<{}>", rust_type(types, options)?)),
+                ("array", Some("varint"), _) => {
+                    let item = options.get("type").ok_or_else(unknown)?;
+                    Ok(format!("PrefixedArray<{}>", rust_type(types, item)?))
+                }
+                ("array" | "buffer" | "pstring", ..) => Err(Unsupported::Skipped(format!(
+                    "{kind} without a varint count"
+                ))),
+                (skipped, ..) if SKIPPED_TYPES.contains(&skipped) => {
+                    Err(Unsupported::Skipped(skipped.to_string()))
+                }
+                _ => Err(unknown()),
+            }
+        }
+        _ => Err(unknown()),
+    }
+}
+
+/// `set_protocol` -> `SetProtocol`
+fn struct_name(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// `playerUUID` -> `player_uuid`, `type` -> `r#type`
+fn field_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+
+    if KEYWORDS.contains(&snake.as_str()) {
+        format!("r#{snake}")
+    } else {
+        snake
+    }
+}
@@ -0,0 +1,24 @@
+use std::{env, fs, process::ExitCode};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let [_, input, output] = args.as_slice() else {
+        eprintln!("usage: minecraft_protocol_codegen <protocol.json> <output.rs>");
+        return ExitCode::FAILURE;
+    };
+
+    let result = fs::read_to_string(input)
+        .map_err(|e| e.to_string())
+        .and_then(|json| {
+            minecraft_protocol_codegen::generate(&json, input).map_err(|e| e.to_string())
+        })
+        .and_then(|code| fs::write(output, code).map_err(|e| e.to_string()));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{input}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
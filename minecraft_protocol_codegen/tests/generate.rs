@@ -0,0 +1,113 @@
+use minecraft_protocol_codegen::{CodegenError, generate};
+
+const SOURCE: &str = "minecraft_protocol_codegen/data/pc/1.21/protocol.json";
+
+#[test]
+fn vendored_output_is_up_to_date() {
+    let json = include_str!("../data/pc/1.21/protocol.json");
+    let expected = include_str!("../../src/generated/v1_21.rs");
+
+    assert_eq!(
+        generate(json, SOURCE).unwrap(),
+        expected,
+        "src/generated/v1_21.rs is stale, rerun minecraft_protocol_codegen"
+    );
+}
+
+#[test]
+fn maps_types_and_names() {
+    let json = r#"{
+        "types": {
+            "varint": "native",
+            "string": ["pstring", { "countType": "varint" }],
+            "position": ["bitfield", [{ "name": "x", "size": 26, "signed": true }]]
+        },
+        "play": {
+            "toClient": { "types": {
+                "packet_set_type": ["container", [
+                    { "name": "type", "type": "string" },
+                    { "name": "entityID", "type": "varint" },
+                    { "name": "isUUIDSet", "type": "bool" }
+                ]],
+                "packet_tags": ["container", [
+                    { "name": "tags", "type": ["array", { "countType": "varint", "type": "string" }] },
+                    { "name": "salt", "type": ["option", ["buffer", { "count": 8 }]] },
+                    { "name": "data", "type": "restBuffer" }
+                ]],
+                "packet_move": ["container", [
+                    { "name": "location", "type": "position" }
+                ]],
+                "packet": ["container", [
+                    { "name": "name", "type": ["mapper", { "type": "varint", "mappings": {
+                        "0x2a": "set_type", "0x10": "tags", "0x11": "move"
+                    } }] }
+                ]]
+            } },
+            "toServer": { "types": {
+                "packet": ["container", [
+                    { "name": "name", "type": ["mapper", { "type": "varint", "mappings": {} }] }
+                ]]
+            } }
+        }
+    }"#;
+
+    let code = generate(json, "test").unwrap();
+    assert!(code.contains("#[packet(0x2A)]\n        pub struct SetType {"));
+    assert!(code.contains("pub r#type: String,"));
+    assert!(code.contains("pub entity_id: VarInt,"));
+    assert!(code.contains("pub is_uuid_set: bool,"));
+    assert!(code.contains("pub tags: PrefixedArray<String>,"));
+    assert!(code.contains("pub salt: Option<[u8; 8]>,"));
+    assert!(code.contains("pub data: RestBuffer,"));
+    assert!(
+        code.contains("use crate::{Packet, ser::{PrefixedArray, RestBuffer}, varint::VarInt};")
+    );
+    assert!(code.contains("// - 0x11 move (position)"));
+    assert!(code.contains("pub mod serverbound {}"));
+}
+
+#[test]
+fn rejects_unknown_types() {
+    let json = r#"{
+        "types": { "varint": "native", "slot": "native" },
+        "play": {
+            "toClient": { "types": {
+                "packet_set_slot": ["container", [
+                    { "name": "item", "type": "slot" }
+                ]],
+                "packet": ["container", [
+                    { "name": "name", "type": ["mapper", { "type": "varint", "mappings": { "0x00": "set_slot" } }] }
+                ]]
+            } },
+            "toServer": { "types": {
+                "packet": ["container", [
+                    { "name": "name", "type": ["mapper", { "type": "varint", "mappings": {} }] }
+                ]]
+            } }
+        }
+    }"#;
+
+    assert!(matches!(
+        generate(json, "test"),
+        Err(CodegenError::UnsupportedType { ty, .. }) if ty == "slot"
+    ));
+}
+
+#[test]
+fn rejects_invalid_packet_ids() {
+    let json = r#"{
+        "types": {},
+        "status": {
+            "toServer": { "types": {
+                "packet": ["container", [
+                    { "name": "name", "type": ["mapper", { "type": "varint", "mappings": { "zero": "ping" } }] }
+                ]]
+            } }
+        }
+    }"#;
+
+    assert!(matches!(
+        generate(json, "test"),
+        Err(CodegenError::InvalidPacketId(id)) if id == "zero"
+    ));
+}
@@ -1,68 +1,592 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{DeriveInput, parse_macro_input};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Field, Fields, GenericParam, Generics, Ident, Index,
+    LitInt, Token, parse::ParseStream, parse_macro_input, parse_quote,
+};
 
 #[proc_macro_derive(Packet, attributes(packet))]
 pub fn derive_packet(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    expand_packet(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Диапазон версий протокола, обе границы включительно.
+#[derive(Default, Clone, Copy)]
+struct VersionRange {
+    since: Option<i32>,
+    until: Option<i32>,
+}
+
+impl VersionRange {
+    fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    fn overlaps(&self, other: &VersionRange) -> bool {
+        let lo = self
+            .since
+            .unwrap_or(i32::MIN)
+            .max(other.since.unwrap_or(i32::MIN));
+        let hi = self
+            .until
+            .unwrap_or(i32::MAX)
+            .min(other.until.unwrap_or(i32::MAX));
+        lo <= hi
+    }
+
+    /// Условие `since <= protocol_version <= until` для сгенерированного кода.
+    fn condition(&self) -> TokenStream2 {
+        let since = option_tokens(self.since);
+        let until = option_tokens(self.until);
+        quote! {
+            minecraft_protocol::packet::in_version_range(protocol_version, #since, #until)
+        }
+    }
+}
+
+fn option_tokens(value: Option<i32>) -> TokenStream2 {
+    match value {
+        Some(v) => quote! { Some(#v) },
+        None => quote! { None },
+    }
+}
+
+struct PacketId {
+    id: Expr,
+    range: VersionRange,
+    /// `#[packet(0x00)]` без ключей
+    positional: bool,
+    attr: Attribute,
+}
+
+struct PacketField<'a> {
+    field: &'a Field,
+    accessor: TokenStream2,
+    range: VersionRange,
+}
+
+fn expand_packet(input: &DeriveInput) -> syn::Result<TokenStream2> {
     let struct_name = &input.ident;
 
-    // Поиск атрибута #[packet]
-    let packet_id_attr = input
-        .attrs
+    let packet_ids = parse_packet_ids(input)?;
+
+    let fields = struct_fields(input, "Packet")?;
+    let packet_fields = fields
         .iter()
-        .find(|attr| attr.path().is_ident("packet"))
-        .expect("Expected #[packet(ID)] attribute");
-
-    // Парсинг значения ID напрямую в токены
-    let packet_id_value = packet_id_attr
-        .parse_args::<syn::Expr>()
-        .expect("Expected packet ID as integer expression, e.g., #[packet(0x00)]");
-
-    // Обработка полей структуры
-    let fields = match &input.data {
-        syn::Data::Struct(syn::DataStruct {
-            fields: syn::Fields::Named(fields),
-            ..
-        }) => &fields.named,
-        _ => panic!("Packet can only be derived for structs with named fields"),
-    };
+        .zip(field_accessors(fields))
+        .map(|(field, accessor)| {
+            Ok(PacketField {
+                field,
+                accessor,
+                range: parse_field_range(field)?,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // Без версий генерируется прежний API: PACKET_ID, as_uncompressed и Deserialize
+    let unversioned = packet_ids.len() == 1
+        && packet_ids[0].range.is_unbounded()
+        && packet_fields.iter().all(|f| f.range.is_unbounded());
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let ser_generics = with_bound(
+        &input.generics,
+        parse_quote!(minecraft_protocol::ser::Serialize),
+    );
+    let (ser_impl_generics, _, ser_where_clause) = ser_generics.split_for_impl();
+
+    let mut de_generics = with_bound(
+        &input.generics,
+        parse_quote!(minecraft_protocol::ser::Deserialize),
+    );
+    // Поля, отсутствующие в версии, заполняются через Default
+    for f in packet_fields.iter().filter(|f| !f.range.is_unbounded()) {
+        let ty = &f.field.ty;
+        de_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: Default));
+    }
+    let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+
+    let packet_generics = with_bound(
+        &de_generics,
+        parse_quote!(minecraft_protocol::ser::Serialize),
+    );
+    let (packet_impl_generics, _, packet_where_clause) = packet_generics.split_for_impl();
+
+    let id_checks = packet_ids.iter().map(|p| {
+        let id = &p.id;
+        let condition = p.range.condition();
+        quote! {
+            if #condition {
+                return Some(minecraft_protocol::varint::VarInt(#id as i32));
+            }
+        }
+    });
+
+    let versioned_writes = packet_fields.iter().map(|f| {
+        let accessor = &f.accessor;
+        let write = quote! {
+            minecraft_protocol::ser::Serialize::serialize(&self.#accessor, &mut payload)?;
+        };
+        if f.range.is_unbounded() {
+            write
+        } else {
+            let condition = f.range.condition();
+            quote! { if #condition { #write } }
+        }
+    });
 
-    // Генерация идентификаторов и типов полей
-    let field_idents: Vec<_> = fields.iter().filter_map(|f| f.ident.as_ref()).collect();
+    let versioned_constructor = constructor(fields, &packet_fields, |f| {
+        let ty = &f.field.ty;
+        let read = quote! {
+            <#ty as minecraft_protocol::ser::Deserialize>::deserialize(reader)?
+        };
+        if f.range.is_unbounded() {
+            read
+        } else {
+            let condition = f.range.condition();
+            quote! {
+                if #condition { #read } else { <#ty as Default>::default() }
+            }
+        }
+    });
 
-    let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let reader = reader_ident(fields);
 
-    let expanded = quote! {
-        impl #struct_name {
-            pub const PACKET_ID: minecraft_protocol::varint::VarInt =
-                minecraft_protocol::varint::VarInt(#packet_id_value as i32);
+    let mut expanded = quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Returns the packet ID for `protocol_version`, or `None` if the packet
+            /// does not exist in that version.
+            pub fn packet_id(protocol_version: i32) -> Option<minecraft_protocol::varint::VarInt> {
+                #(#id_checks)*
+                None
+            }
         }
 
-        impl #struct_name {
-            pub fn as_uncompressed(
+        impl #ser_impl_generics #struct_name #ty_generics #ser_where_clause {
+            pub fn as_uncompressed_versioned(
                 &self,
+                protocol_version: i32,
             ) -> Result<minecraft_protocol::packet::UncompressedPacket, minecraft_protocol::ser::SerializationError> {
+                let packet_id = Self::packet_id(protocol_version).ok_or(
+                    minecraft_protocol::ser::SerializationError::UnsupportedVersion(protocol_version),
+                )?;
+                #[allow(unused_mut)]
                 let mut payload = Vec::new();
-                #(minecraft_protocol::ser::Serialize::serialize(&self.#field_idents, &mut payload)?;)*
+                #(#versioned_writes)*
                 Ok(minecraft_protocol::packet::UncompressedPacket {
-                    packet_id: Self::PACKET_ID.clone(),
+                    packet_id,
                     payload
                 })
             }
         }
 
-        impl minecraft_protocol::ser::Deserialize for #struct_name {
-            fn deserialize<R: std::io::Read + Unpin>(reader: &mut R) -> Result<Self, minecraft_protocol::ser::SerializationError> {
-                Ok(Self {
-                    #(#field_idents: <#field_types as minecraft_protocol::ser::Deserialize>::deserialize(reader)?,)*
+        impl #de_impl_generics #struct_name #ty_generics #de_where_clause {
+            pub fn deserialize_versioned<R: std::io::Read + Unpin>(
+                #reader: &mut R,
+                protocol_version: i32,
+            ) -> Result<Self, minecraft_protocol::ser::SerializationError> {
+                if Self::packet_id(protocol_version).is_none() {
+                    return Err(minecraft_protocol::ser::SerializationError::UnsupportedVersion(protocol_version));
+                }
+                Ok(#versioned_constructor)
+            }
+        }
+
+        impl #packet_impl_generics minecraft_protocol::packet::Packet for #struct_name #ty_generics #packet_where_clause {
+            fn packet_id(protocol_version: i32) -> Option<minecraft_protocol::varint::VarInt> {
+                Self::packet_id(protocol_version)
+            }
+
+            fn encode(
+                &self,
+                protocol_version: i32,
+            ) -> Result<minecraft_protocol::packet::UncompressedPacket, minecraft_protocol::ser::SerializationError> {
+                self.as_uncompressed_versioned(protocol_version)
+            }
+
+            fn decode<R: std::io::Read + Unpin>(
+                reader: &mut R,
+                protocol_version: i32,
+            ) -> Result<Self, minecraft_protocol::ser::SerializationError> {
+                Self::deserialize_versioned(reader, protocol_version)
+            }
+        }
+    };
+
+    if unversioned {
+        let packet_id_value = &packet_ids[0].id;
+        let accessors = packet_fields.iter().map(|f| &f.accessor);
+        let plain_constructor = constructor(fields, &packet_fields, |f| {
+            let ty = &f.field.ty;
+            quote! { <#ty as minecraft_protocol::ser::Deserialize>::deserialize(reader)? }
+        });
+
+        expanded.extend(quote! {
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                pub const PACKET_ID: minecraft_protocol::varint::VarInt =
+                    minecraft_protocol::varint::VarInt(#packet_id_value as i32);
+            }
+
+            impl #ser_impl_generics #struct_name #ty_generics #ser_where_clause {
+                pub fn as_uncompressed(
+                    &self,
+                ) -> Result<minecraft_protocol::packet::UncompressedPacket, minecraft_protocol::ser::SerializationError> {
+                    #[allow(unused_mut)]
+                    let mut payload = Vec::new();
+                    #(minecraft_protocol::ser::Serialize::serialize(&self.#accessors, &mut payload)?;)*
+                    Ok(minecraft_protocol::packet::UncompressedPacket {
+                        packet_id: Self::PACKET_ID.clone(),
+                        payload
+                    })
+                }
+            }
+
+            impl #de_impl_generics minecraft_protocol::ser::Deserialize for #struct_name #ty_generics #de_where_clause {
+                fn deserialize<R: std::io::Read + Unpin>(#reader: &mut R) -> Result<Self, minecraft_protocol::ser::SerializationError> {
+                    Ok(#plain_constructor)
+                }
+            }
+        });
+    }
+
+    Ok(expanded)
+}
+
+/// `#[derive(Serialize)]`: поля сериализуются по порядку объявления.
+#[proc_macro_derive(Serialize)]
+pub fn derive_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_serialize(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// `#[derive(Deserialize)]`: поля читаются по порядку объявления.
+#[proc_macro_derive(Deserialize)]
+pub fn derive_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_deserialize(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_serialize(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let fields = struct_fields(input, "Serialize")?;
+    let accessors = field_accessors(fields);
+
+    let generics = with_bound(
+        &input.generics,
+        parse_quote!(minecraft_protocol::ser::Serialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let writer = if fields.is_empty() {
+        format_ident!("_writer")
+    } else {
+        format_ident!("writer")
+    };
+
+    Ok(quote! {
+        impl #impl_generics minecraft_protocol::ser::Serialize for #struct_name #ty_generics #where_clause {
+            fn serialize<W: std::io::Write + Unpin>(&self, #writer: &mut W) -> Result<(), minecraft_protocol::ser::SerializationError> {
+                #(minecraft_protocol::ser::Serialize::serialize(&self.#accessors, #writer)?;)*
+                Ok(())
+            }
+        }
+    })
+}
+
+fn expand_deserialize(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let fields = struct_fields(input, "Deserialize")?;
+    let accessors = field_accessors(fields);
+
+    let generics = with_bound(
+        &input.generics,
+        parse_quote!(minecraft_protocol::ser::Deserialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let values = fields.iter().map(|f| {
+        let ty = &f.ty;
+        quote! { <#ty as minecraft_protocol::ser::Deserialize>::deserialize(reader)? }
+    });
+    let constructor = match fields {
+        Fields::Named(_) => quote! { Self { #(#accessors: #values,)* } },
+        Fields::Unnamed(_) => quote! { Self( #(#values,)* ) },
+        Fields::Unit => quote! { Self },
+    };
+
+    let reader = reader_ident(fields);
+
+    Ok(quote! {
+        impl #impl_generics minecraft_protocol::ser::Deserialize for #struct_name #ty_generics #where_clause {
+            fn deserialize<R: std::io::Read + Unpin>(#reader: &mut R) -> Result<Self, minecraft_protocol::ser::SerializationError> {
+                Ok(#constructor)
+            }
+        }
+    })
+}
+
+/// Поля структуры или ошибка для перечислений и объединений.
+fn struct_fields<'a>(input: &'a DeriveInput, derive: &str) -> syn::Result<&'a Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        Data::Enum(data) => Err(syn::Error::new(
+            data.enum_token.span,
+            format!("{derive} can only be derived for structs, not enums"),
+        )),
+        Data::Union(data) => Err(syn::Error::new(
+            data.union_token.span,
+            format!("{derive} can only be derived for structs, not unions"),
+        )),
+    }
+}
+
+/// Доступ к полям через self.name или self.0 для кортежных структур
+fn field_accessors(fields: &Fields) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => Index::from(i).into_token_stream(),
+        })
+        .collect()
+}
+
+/// Параметр reader не используется для структур без полей
+fn reader_ident(fields: &Fields) -> Ident {
+    if fields.is_empty() {
+        format_ident!("_reader")
+    } else {
+        format_ident!("reader")
+    }
+}
+
+/// Собирает `Self { .. }`, `Self(..)` или `Self` из выражений для каждого поля.
+fn constructor(
+    fields: &Fields,
+    packet_fields: &[PacketField],
+    value: impl Fn(&PacketField) -> TokenStream2,
+) -> TokenStream2 {
+    let values = packet_fields.iter().map(value);
+    match fields {
+        Fields::Named(_) => {
+            let accessors = packet_fields.iter().map(|f| &f.accessor);
+            quote! { Self { #(#accessors: #values,)* } }
+        }
+        Fields::Unnamed(_) => quote! { Self( #(#values,)* ) },
+        Fields::Unit => quote! { Self },
+    }
+}
+
+/// Ищет `#[packet(...)]` на структуре и возвращает все объявленные ID.
+///
+/// Поддерживаются две формы: `#[packet(0x00)]` и
+/// `#[packet(id = 0x1A, since = 763, until = 765)]`, причём вторую можно
+/// указывать несколько раз для разных диапазонов версий.
+fn parse_packet_ids(input: &DeriveInput) -> syn::Result<Vec<PacketId>> {
+    let mut packet_ids = Vec::new();
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("packet")) {
+        let list = attr.meta.require_list().map_err(|_| {
+            syn::Error::new_spanned(
+                attr,
+                "expected packet ID in parentheses, e.g. #[packet(0x00)]",
+            )
+        })?;
+
+        if list.tokens.is_empty() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "expected packet ID in parentheses, e.g. #[packet(0x00)]",
+            ));
+        }
+
+        let packet_id = attr.parse_args_with(|input: ParseStream| {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                parse_versioned_id(input, attr)
+            } else {
+                let id = input.parse::<Expr>().and_then(|id| {
+                    if input.is_empty() {
+                        Ok(id)
+                    } else {
+                        Err(input.error("unexpected token"))
+                    }
+                });
+                id.map(|id| PacketId {
+                    id,
+                    range: VersionRange::default(),
+                    positional: true,
+                    attr: attr.clone(),
+                })
+                .map_err(|e| {
+                    syn::Error::new(
+                        e.span(),
+                        "expected packet ID as integer expression, e.g. #[packet(0x00)]",
+                    )
                 })
             }
+        })?;
+
+        packet_ids.push(packet_id);
+    }
+
+    if packet_ids.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "missing #[packet(ID)] attribute, e.g. #[packet(0x00)]",
+        ));
+    }
+
+    if packet_ids.len() > 1
+        && let Some(positional) = packet_ids.iter().find(|p| p.positional)
+    {
+        return Err(syn::Error::new_spanned(
+            &positional.attr,
+            "multiple packet IDs must use #[packet(id = .., since = .., until = ..)]",
+        ));
+    }
+
+    for (i, a) in packet_ids.iter().enumerate() {
+        if let Some(b) = packet_ids[i + 1..]
+            .iter()
+            .find(|b| a.range.overlaps(&b.range))
+        {
+            return Err(syn::Error::new_spanned(
+                &b.attr,
+                "version range overlaps with another #[packet] attribute",
+            ));
+        }
+    }
+
+    Ok(packet_ids)
+}
+
+fn parse_versioned_id(input: ParseStream, attr: &Attribute) -> syn::Result<PacketId> {
+    let mut id = None;
+    let mut range = VersionRange::default();
+
+    while !input.is_empty() {
+        let key: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        if key == "id" {
+            if id.is_some() {
+                return Err(syn::Error::new(key.span(), "duplicate `id`"));
+            }
+            id = Some(input.parse::<Expr>()?);
+        } else if !parse_range_key(&key, input, &mut range)? {
+            return Err(syn::Error::new(
+                key.span(),
+                "unknown key, expected `id`, `since` or `until`",
+            ));
+        }
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
         }
+    }
+
+    let id = id.ok_or_else(|| syn::Error::new_spanned(attr, "missing `id = ..`"))?;
+
+    Ok(PacketId {
+        id,
+        range,
+        positional: false,
+        attr: attr.clone(),
+    })
+}
+
+/// Разбирает `since = N` / `until = N`. Возвращает `false` для других ключей.
+fn parse_range_key(key: &Ident, input: ParseStream, range: &mut VersionRange) -> syn::Result<bool> {
+    let slot = if key == "since" {
+        &mut range.since
+    } else if key == "until" {
+        &mut range.until
+    } else {
+        return Ok(false);
     };
 
-    TokenStream::from(expanded)
+    if slot.is_some() {
+        return Err(syn::Error::new(key.span(), format!("duplicate `{key}`")));
+    }
+
+    let value: LitInt = input.parse()?;
+    *slot = Some(value.base10_parse::<i32>()?);
+
+    if let (Some(since), Some(until)) = (range.since, range.until)
+        && since > until
+    {
+        return Err(syn::Error::new(
+            value.span(),
+            "`since` must not be greater than `until`",
+        ));
+    }
+
+    Ok(true)
+}
+
+/// Разбирает `#[packet(since = .., until = ..)]` на поле.
+fn parse_field_range(field: &Field) -> syn::Result<VersionRange> {
+    let mut range = VersionRange::default();
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("packet")) {
+        attr.parse_args_with(|input: ParseStream| {
+            if input.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `since = ..` and/or `until = ..`",
+                ));
+            }
+
+            while !input.is_empty() {
+                let key: Ident = input.parse().map_err(|e| {
+                    syn::Error::new(e.span(), "expected `since = ..` and/or `until = ..`")
+                })?;
+                input.parse::<Token![=]>()?;
+
+                if !parse_range_key(&key, input, &mut range)? {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "unknown key, expected `since` or `until`",
+                    ));
+                }
+
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(range)
+}
+
+/// Добавляет `bound` ко всем параметрам-типам структуры.
+fn with_bound(generics: &Generics, bound: syn::TypeParamBound) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(type_param) = param {
+            type_param.bounds.push(bound.clone());
+        }
+    }
+    generics
 }
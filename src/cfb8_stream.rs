@@ -1,10 +1,6 @@
-use openssl::symm::{Cipher, Crypter, Mode};
-use std::io;
-use std::pin::Pin;
-use std::task::{Context, Poll};
-use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
-use tokio::net::TcpStream;
-use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use std::io::{self, Read, Write};
+
+use crate::cipher::{Cfb8Cipher, DefaultCipher, Direction};
 
 pub struct CFB8Stream<R, W> {
     pub read_stream: CFB8ReadHalf<R>,
@@ -13,40 +9,73 @@ pub struct CFB8Stream<R, W> {
 
 pub struct CFB8ReadHalf<R> {
     read_half: R,
-    decrypter: Crypter,
+    decrypter: DefaultCipher,
 }
 
+/// Encrypted bytes the inner writer hasn't taken yet are kept in a reusable
+/// buffer and sent before anything else, so the cipher only ever sees each
+/// byte once. Call `flush` to push them out.
 pub struct CFB8WriteHalf<W> {
     write_half: W,
-    encrypter: Crypter,
+    encrypter: DefaultCipher,
+    pending: Vec<u8>,
+    pending_pos: usize,
 }
 
 impl<W> CFB8WriteHalf<W> {
     pub fn new(write_half: W, key: &[u8; 16]) -> io::Result<Self> {
-        let encrypter = Self::get_encrypter(key)?;
+        let encrypter = DefaultCipher::new(key, Direction::Encrypt)?;
 
         Ok(Self {
             write_half,
             encrypter,
+            pending: Vec::new(),
+            pending_pos: 0,
         })
     }
 
-    fn get_encrypter(key: &[u8; 16]) -> io::Result<Crypter> {
-        let cipher = Cipher::aes_128_cfb8();
+    /// Returns the inner writer. Bytes that were not flushed are lost.
+    pub fn into_inner(self) -> W {
+        self.write_half
+    }
 
-        let mut encrypter = Crypter::new(cipher, Mode::Encrypt, key, Some(key))?;
-        encrypter.pad(false);
-        Ok(encrypter)
+    fn pending(&self) -> &[u8] {
+        &self.pending[self.pending_pos..]
     }
 
-    pub fn into_inner(self) -> W {
-        self.write_half
+    /// Encrypts `buf` into the pending buffer, which must be empty.
+    fn queue(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.pending.clear();
+        self.pending_pos = 0;
+        self.pending.extend_from_slice(buf);
+        self.encrypter.apply(&mut self.pending)
+    }
+
+    fn advance(&mut self, count: usize) -> io::Result<()> {
+        if count == 0 {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        self.pending_pos += count;
+        Ok(())
+    }
+}
+
+impl<W: Write> CFB8WriteHalf<W> {
+    fn drain(&mut self) -> io::Result<()> {
+        while !self.pending().is_empty() {
+            match self.write_half.write(&self.pending[self.pending_pos..]) {
+                Ok(count) => self.advance(count)?,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
     }
 }
 
 impl<R> CFB8ReadHalf<R> {
     pub fn new(read_half: R, key: &[u8; 16]) -> io::Result<Self> {
-        let decrypter = Self::get_decrypter(key)?;
+        let decrypter = DefaultCipher::new(key, Direction::Decrypt)?;
 
         Ok(Self {
             read_half,
@@ -54,14 +83,6 @@ impl<R> CFB8ReadHalf<R> {
         })
     }
 
-    fn get_decrypter(key: &[u8; 16]) -> io::Result<Crypter> {
-        let cipher = Cipher::aes_128_cfb8();
-
-        let mut decrypter = Crypter::new(cipher, Mode::Decrypt, key, Some(key))?;
-        decrypter.pad(false);
-        Ok(decrypter)
-    }
-
     pub fn into_inner(self) -> R {
         self.read_half
     }
@@ -88,108 +109,308 @@ impl<R, W> CFB8Stream<R, W> {
     }
 }
 
-impl CFB8Stream<OwnedReadHalf, OwnedWriteHalf> {
-    pub fn new_from_tcp(
-        stream: TcpStream,
-        key: &[u8; 16],
-    ) -> io::Result<CFB8Stream<OwnedReadHalf, OwnedWriteHalf>> {
-        let (read_half, write_half) = stream.into_split();
+impl CFB8Stream<std::net::TcpStream, std::net::TcpStream> {
+    /// Blocking counterpart of `new_from_tcp`.
+    /// Both halves share the socket through [`std::net::TcpStream::try_clone`].
+    pub fn new_from_std_tcp(stream: std::net::TcpStream, key: &[u8; 16]) -> io::Result<Self> {
+        let read_half = stream.try_clone()?;
 
-        CFB8Stream::new(read_half, write_half, key)
+        CFB8Stream::new(read_half, stream, key)
     }
 }
 
-impl<R> AsyncRead for CFB8ReadHalf<R>
-where
-    R: AsyncRead + Unpin,
-{
-    fn poll_read(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut ReadBuf<'_>,
-    ) -> Poll<io::Result<()>> {
-        let pre_len = buf.filled().len();
-        let poll = Pin::new(&mut self.read_half).poll_read(cx, buf);
-
-        if let Poll::Ready(Ok(())) = poll {
-            let new_data = &mut buf.filled_mut()[pre_len..];
-            let mut output = vec![0; new_data.len()];
-            self.decrypter
-                .update(new_data, &mut output)
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-            new_data.copy_from_slice(&output);
-        }
-
-        poll
+impl<R: Read> Read for CFB8ReadHalf<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.read_half.read(buf)?;
+        self.decrypter.apply(&mut buf[..count])?;
+        Ok(count)
     }
 }
 
-impl<W> AsyncWrite for CFB8WriteHalf<W>
-where
-    W: AsyncWrite + Unpin,
-{
-    fn poll_write(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &[u8],
-    ) -> Poll<Result<usize, io::Error>> {
-        let mut encrypted = vec![0; buf.len() + 16];
-        let count = self
-            .encrypter
-            .update(buf, &mut encrypted)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-        encrypted.truncate(count);
+impl<W: Write> Write for CFB8WriteHalf<W> {
+    /// Once `buf` is encrypted it is queued. If the inner writer would block,
+    /// the rest stays pending and `buf` still counts as written. Any other
+    /// error is returned right away with `buf` already queued, so callers
+    /// must `flush` to retry instead of writing `buf` again.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.drain()?;
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        self.queue(buf)?;
+        match self.drain() {
+            Err(e) if e.kind() != io::ErrorKind::WouldBlock => Err(e),
+            _ => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.drain()?;
+        self.write_half.flush()
+    }
+}
 
-        Pin::new(&mut self.write_half).poll_write(cx, &encrypted)
+impl<R: Read, W> Read for CFB8Stream<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_stream.read(buf)
     }
+}
 
-    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
-        Pin::new(&mut self.write_half).poll_flush(cx)
+impl<R, W: Write> Write for CFB8Stream<R, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_stream.write(buf)
     }
 
-    fn poll_shutdown(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-    ) -> Poll<Result<(), io::Error>> {
-        Pin::new(&mut self.write_half).poll_shutdown(cx)
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_stream.flush()
     }
 }
 
-impl<R, W> AsyncRead for CFB8Stream<R, W>
-where
-    R: AsyncRead + Unpin,
-    W: AsyncWrite + Unpin,
-{
-    fn poll_read(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut ReadBuf<'_>,
-    ) -> Poll<io::Result<()>> {
-        AsyncRead::poll_read(Pin::new(&mut self.read_stream), cx, buf)
+#[cfg(feature = "tokio")]
+mod tokio_io {
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, ready};
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+    use tokio::net::TcpStream;
+    use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+
+    use super::{CFB8ReadHalf, CFB8Stream, CFB8WriteHalf};
+    use crate::cipher::Cfb8Cipher;
+
+    impl CFB8Stream<OwnedReadHalf, OwnedWriteHalf> {
+        pub fn new_from_tcp(
+            stream: TcpStream,
+            key: &[u8; 16],
+        ) -> io::Result<CFB8Stream<OwnedReadHalf, OwnedWriteHalf>> {
+            let (read_half, write_half) = stream.into_split();
+
+            CFB8Stream::new(read_half, write_half, key)
+        }
+    }
+
+    impl<R> AsyncRead for CFB8ReadHalf<R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let pre_len = buf.filled().len();
+            let poll = Pin::new(&mut self.read_half).poll_read(cx, buf);
+
+            if let Poll::Ready(Ok(())) = poll {
+                self.decrypter.apply(&mut buf.filled_mut()[pre_len..])?;
+            }
+
+            poll
+        }
+    }
+
+    impl<W: AsyncWrite + Unpin> CFB8WriteHalf<W> {
+        fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            while !self.pending().is_empty() {
+                let pending = &self.pending[self.pending_pos..];
+                let count = ready!(Pin::new(&mut self.write_half).poll_write(cx, pending))?;
+                self.advance(count)?;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<W> AsyncWrite for CFB8WriteHalf<W>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        /// Returns `Pending` only while earlier bytes are still pending.
+        /// Once `buf` is encrypted it counts as written unless sending it
+        /// fails, see the blocking implementation.
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize, io::Error>> {
+            ready!(self.poll_drain(cx))?;
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            self.queue(buf)?;
+            match self.poll_drain(cx) {
+                Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+                _ => Poll::Ready(Ok(buf.len())),
+            }
+        }
+
+        fn poll_flush(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Result<(), io::Error>> {
+            ready!(self.poll_drain(cx))?;
+            Pin::new(&mut self.write_half).poll_flush(cx)
+        }
+
+        fn poll_shutdown(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Result<(), io::Error>> {
+            ready!(self.poll_drain(cx))?;
+            Pin::new(&mut self.write_half).poll_shutdown(cx)
+        }
+    }
+
+    impl<R, W> AsyncRead for CFB8Stream<R, W>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            AsyncRead::poll_read(Pin::new(&mut self.read_stream), cx, buf)
+        }
+    }
+
+    impl<R, W> AsyncWrite for CFB8Stream<R, W>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize, io::Error>> {
+            AsyncWrite::poll_write(Pin::new(&mut self.write_stream), cx, buf)
+        }
+
+        fn poll_flush(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Result<(), io::Error>> {
+            AsyncWrite::poll_flush(Pin::new(&mut self.write_stream), cx)
+        }
+
+        fn poll_shutdown(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Result<(), io::Error>> {
+            AsyncWrite::poll_shutdown(Pin::new(&mut self.write_stream), cx)
+        }
     }
 }
 
-impl<R, W> AsyncWrite for CFB8Stream<R, W>
-where
-    R: AsyncRead + Unpin,
-    W: AsyncWrite + Unpin,
-{
-    fn poll_write(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &[u8],
-    ) -> Poll<Result<usize, io::Error>> {
-        AsyncWrite::poll_write(Pin::new(&mut self.write_stream), cx, buf)
+#[cfg(feature = "futures-io")]
+mod futures_io_impls {
+    use futures_io::{AsyncRead, AsyncWrite};
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, ready};
+
+    use super::{CFB8ReadHalf, CFB8Stream, CFB8WriteHalf};
+    use crate::cipher::Cfb8Cipher;
+
+    impl<R> AsyncRead for CFB8ReadHalf<R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let poll = Pin::new(&mut self.read_half).poll_read(cx, buf);
+
+            if let Poll::Ready(Ok(count)) = poll {
+                self.decrypter.apply(&mut buf[..count])?;
+            }
+
+            poll
+        }
     }
 
-    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
-        AsyncWrite::poll_flush(Pin::new(&mut self.write_stream), cx)
+    impl<W: AsyncWrite + Unpin> CFB8WriteHalf<W> {
+        fn poll_drain_futures(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            while !self.pending().is_empty() {
+                let pending = &self.pending[self.pending_pos..];
+                let count = ready!(Pin::new(&mut self.write_half).poll_write(cx, pending))?;
+                self.advance(count)?;
+            }
+            Poll::Ready(Ok(()))
+        }
     }
 
-    fn poll_shutdown(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-    ) -> Poll<Result<(), io::Error>> {
-        AsyncWrite::poll_shutdown(Pin::new(&mut self.write_stream), cx)
+    impl<W> AsyncWrite for CFB8WriteHalf<W>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        /// Same contract as the tokio implementation.
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            ready!(self.poll_drain_futures(cx))?;
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            self.queue(buf)?;
+            match self.poll_drain_futures(cx) {
+                Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+                _ => Poll::Ready(Ok(buf.len())),
+            }
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            ready!(self.poll_drain_futures(cx))?;
+            Pin::new(&mut self.write_half).poll_flush(cx)
+        }
+
+        fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            ready!(self.poll_drain_futures(cx))?;
+            Pin::new(&mut self.write_half).poll_close(cx)
+        }
+    }
+
+    impl<R, W> AsyncRead for CFB8Stream<R, W>
+    where
+        R: AsyncRead + Unpin,
+        W: Unpin,
+    {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            AsyncRead::poll_read(Pin::new(&mut self.read_stream), cx, buf)
+        }
+    }
+
+    impl<R, W> AsyncWrite for CFB8Stream<R, W>
+    where
+        R: Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            AsyncWrite::poll_write(Pin::new(&mut self.write_stream), cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            AsyncWrite::poll_flush(Pin::new(&mut self.write_stream), cx)
+        }
+
+        fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            AsyncWrite::poll_close(Pin::new(&mut self.write_stream), cx)
+        }
     }
 }
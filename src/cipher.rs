@@ -0,0 +1,85 @@
+//! AES-128-CFB8 backends.
+//!
+//! The `openssl` feature (default) uses OpenSSL, the `rustcrypto` feature
+//! uses the pure-Rust `aes` and `cfb8` crates. Both produce identical output.
+//! [`DefaultCipher`] is the one used by [`cfb8_stream`](crate::cfb8_stream)
+//! and the connection; OpenSSL wins if both features are enabled. With
+//! neither, this module and the ones built on it are left out.
+
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// AES-128-CFB8 with the shared secret as both key and IV, as Minecraft uses it.
+pub trait Cfb8Cipher: Sized + Send {
+    fn new(key: &[u8; 16], direction: Direction) -> io::Result<Self>;
+
+    /// Encrypts or decrypts `data` in place and advances the cipher state.
+    fn apply(&mut self, data: &mut [u8]) -> io::Result<()>;
+}
+
+#[cfg(feature = "openssl")]
+pub type DefaultCipher = OpensslCipher;
+
+#[cfg(all(feature = "rustcrypto", not(feature = "openssl")))]
+pub type DefaultCipher = RustCryptoCipher;
+
+#[cfg(feature = "openssl")]
+pub struct OpensslCipher(openssl::cipher_ctx::CipherCtx);
+
+#[cfg(feature = "openssl")]
+impl Cfb8Cipher for OpensslCipher {
+    fn new(key: &[u8; 16], direction: Direction) -> io::Result<Self> {
+        use openssl::{cipher::Cipher, cipher_ctx::CipherCtx};
+
+        let cipher = Some(Cipher::aes_128_cfb8());
+        let mut ctx = CipherCtx::new()?;
+        match direction {
+            Direction::Encrypt => ctx.encrypt_init(cipher, Some(key), Some(key))?,
+            Direction::Decrypt => ctx.decrypt_init(cipher, Some(key), Some(key))?,
+        }
+        ctx.set_padding(false);
+        Ok(Self(ctx))
+    }
+
+    fn apply(&mut self, data: &mut [u8]) -> io::Result<()> {
+        // CFB8 has a block size of 1, so the output fits in the input buffer
+        let len = data.len();
+        self.0.cipher_update_inplace(data, len)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rustcrypto")]
+pub enum RustCryptoCipher {
+    Encrypt(cfb8::Encryptor<aes::Aes128>),
+    Decrypt(cfb8::Decryptor<aes::Aes128>),
+}
+
+#[cfg(feature = "rustcrypto")]
+impl Cfb8Cipher for RustCryptoCipher {
+    fn new(key: &[u8; 16], direction: Direction) -> io::Result<Self> {
+        use aes::cipher::KeyIvInit;
+
+        Ok(match direction {
+            Direction::Encrypt => Self::Encrypt(cfb8::Encryptor::new(key.into(), key.into())),
+            Direction::Decrypt => Self::Decrypt(cfb8::Decryptor::new(key.into(), key.into())),
+        })
+    }
+
+    fn apply(&mut self, data: &mut [u8]) -> io::Result<()> {
+        use aes::cipher::{BlockDecryptMut, BlockEncryptMut, inout::InOutBuf};
+
+        // CFB8 blocks are one byte long, so there is never a tail
+        let (blocks, _) = InOutBuf::from(data).into_chunks();
+        match self {
+            Self::Encrypt(encryptor) => encryptor.encrypt_blocks_inout_mut(blocks),
+            Self::Decrypt(decryptor) => decryptor.decrypt_blocks_inout_mut(blocks),
+        }
+        Ok(())
+    }
+}
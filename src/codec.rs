@@ -0,0 +1,218 @@
+//! [`tokio_util::codec`] framing for Minecraft packets.
+//!
+//! ```no_run
+//! # async fn example(stream: tokio::net::TcpStream) -> Result<(), minecraft_protocol::packet::PacketError> {
+//! use futures::StreamExt;
+//! use minecraft_protocol::codec::MinecraftCodec;
+//! use tokio_util::codec::Framed;
+//!
+//! let mut framed = Framed::new(stream, MinecraftCodec::new());
+//! while let Some(packet) = framed.next().await {
+//!     let packet = packet?;
+//!     // After Set Compression; bytes already buffered are decoded with the new threshold
+//!     framed.codec_mut().set_compression(Some(256));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, Cursor, Read, Write};
+
+use bytes::{Buf, BufMut, BytesMut};
+use flate2::{Compression, read::ZlibDecoder, write::ZlibEncoder};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{
+    packet::{PacketError, UncompressedPacket},
+    varint::{VarInt, VarIntError},
+};
+
+/// Largest frame vanilla accepts, the most a 3-byte VarInt length can hold.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 2_097_151;
+
+/// Largest uncompressed packet vanilla accepts.
+pub const MAX_DATA_LENGTH: usize = 8_388_608;
+
+/// Length-prefixed packet codec with optional zlib compression.
+///
+/// The codec only keeps framing state, so the compression threshold and frame
+/// limit can be changed between packets through `Framed::codec_mut` without
+/// dropping bytes that were already read.
+#[derive(Debug, Clone)]
+pub struct MinecraftCodec {
+    compression_threshold: Option<i32>,
+    max_frame_size: usize,
+}
+
+impl MinecraftCodec {
+    pub fn new() -> Self {
+        Self {
+            compression_threshold: None,
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+
+    pub fn with_compression(mut self, threshold: Option<i32>) -> Self {
+        self.set_compression(threshold);
+        self
+    }
+
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Negative thresholds disable compression, same as in Set Compression.
+    pub fn set_compression(&mut self, threshold: Option<i32>) {
+        self.compression_threshold = threshold.filter(|threshold| *threshold >= 0);
+    }
+
+    pub fn compression(&self) -> Option<i32> {
+        self.compression_threshold
+    }
+
+    pub fn set_max_frame_size(&mut self, max_frame_size: usize) {
+        self.max_frame_size = max_frame_size;
+    }
+
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    fn decode_frame(&self, frame: &[u8]) -> Result<UncompressedPacket, PacketError> {
+        let Some(threshold) = self.compression_threshold else {
+            return split_packet(frame);
+        };
+
+        let mut cursor = Cursor::new(frame);
+        let data_length = VarInt::read_sync(&mut cursor)?.0;
+        let compressed = &frame[cursor.position() as usize..];
+
+        if data_length == 0 {
+            return split_packet(compressed);
+        }
+
+        // Vanilla rejects packets that should not have been compressed
+        if data_length < threshold || data_length as usize > MAX_DATA_LENGTH {
+            return Err(PacketError::InvalidDataLength(data_length));
+        }
+
+        let mut data = Vec::with_capacity(data_length as usize);
+        ZlibDecoder::new(compressed)
+            .take(data_length as u64 + 1)
+            .read_to_end(&mut data)?;
+
+        if data.len() != data_length as usize {
+            return Err(PacketError::InvalidDataLength(data_length));
+        }
+
+        split_packet(&data)
+    }
+}
+
+impl Default for MinecraftCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn split_packet(data: &[u8]) -> Result<UncompressedPacket, PacketError> {
+    let mut cursor = Cursor::new(data);
+    let packet_id = VarInt::read_sync(&mut cursor)?;
+
+    Ok(UncompressedPacket {
+        packet_id,
+        payload: data[cursor.position() as usize..].to_vec(),
+    })
+}
+
+/// Reads the frame length without consuming it. `None` means more bytes are needed.
+fn peek_length(src: &[u8]) -> Result<Option<(usize, usize)>, PacketError> {
+    let mut cursor = Cursor::new(src);
+    match VarInt::read_sync(&mut cursor) {
+        Ok(VarInt(len)) if len < 0 => Err(VarIntError::NegativeValue.into()),
+        Ok(VarInt(len)) => Ok(Some((cursor.position() as usize, len as usize))),
+        Err(VarIntError::IOError(e)) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl Decoder for MinecraftCodec {
+    type Item = UncompressedPacket;
+    type Error = PacketError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some((header, len)) = peek_length(src)? else {
+            return Ok(None);
+        };
+
+        if len > self.max_frame_size {
+            return Err(PacketError::FrameTooLarge(len, self.max_frame_size));
+        }
+
+        if src.len() < header + len {
+            src.reserve(header + len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(header);
+        let frame = src.split_to(len);
+        self.decode_frame(&frame).map(Some)
+    }
+}
+
+impl Encoder<&UncompressedPacket> for MinecraftCodec {
+    type Error = PacketError;
+
+    fn encode(
+        &mut self,
+        packet: &UncompressedPacket,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        let mut data = Vec::with_capacity(packet.payload.len() + 5);
+        packet.packet_id.write_sync(&mut data)?;
+        data.extend_from_slice(&packet.payload);
+
+        let frame = match self.compression_threshold {
+            None => data,
+            Some(threshold) if data.len() < threshold as usize => {
+                let mut frame = Vec::with_capacity(data.len() + 1);
+                VarInt(0).write_sync(&mut frame)?;
+                frame.extend_from_slice(&data);
+                frame
+            }
+            Some(_) => {
+                let mut frame = Vec::new();
+                VarInt(data.len() as i32).write_sync(&mut frame)?;
+
+                let mut encoder = ZlibEncoder::new(frame, Compression::default());
+                encoder.write_all(&data)?;
+                encoder.finish()?
+            }
+        };
+
+        if frame.len() > self.max_frame_size {
+            return Err(PacketError::FrameTooLarge(frame.len(), self.max_frame_size));
+        }
+
+        let mut header = Vec::with_capacity(5);
+        VarInt(frame.len() as i32).write_sync(&mut header)?;
+
+        dst.reserve(header.len() + frame.len());
+        dst.put_slice(&header);
+        dst.put_slice(&frame);
+        Ok(())
+    }
+}
+
+impl Encoder<UncompressedPacket> for MinecraftCodec {
+    type Error = PacketError;
+
+    fn encode(
+        &mut self,
+        packet: UncompressedPacket,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        self.encode(&packet, dst)
+    }
+}
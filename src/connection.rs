@@ -0,0 +1,311 @@
+//! A packet stream that keeps track of protocol state, compression and
+//! encryption.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), minecraft_protocol::connection::ConnectionError> {
+//! use minecraft_protocol::connection::Connection;
+//! use minecraft_protocol::packets::{handshake::serverbound::Handshake, status};
+//! use minecraft_protocol::varint::VarInt;
+//!
+//! let stream = tokio::net::TcpStream::connect("example.com:25565").await?;
+//! let mut connection = Connection::client(stream);
+//!
+//! connection
+//!     .send(&Handshake {
+//!         protocol_version: VarInt(connection.protocol_version()),
+//!         server_address: "example.com".to_string(),
+//!         server_port: 25565,
+//!         next_state: Handshake::STATUS,
+//!     })
+//!     .await?;
+//! connection.send(&status::serverbound::StatusRequest).await?;
+//! let response: status::clientbound::StatusResponse = connection.recv().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, Cursor};
+
+use bytes::BytesMut;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio_util::codec::{Decoder, Encoder};
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+use crate::cipher::{Cfb8Cipher, DefaultCipher, Direction};
+use crate::{
+    codec::MinecraftCodec,
+    packet::{Packet, PacketError, UncompressedPacket},
+    packets::{
+        PROTOCOL_VERSION,
+        configuration::serverbound::AcknowledgeFinishConfiguration,
+        handshake::serverbound::Handshake,
+        login::{clientbound::SetCompression, serverbound::LoginAcknowledged},
+        play::serverbound::AcknowledgeConfiguration,
+    },
+    ser::SerializationError,
+};
+
+#[derive(Debug, Error)]
+pub enum ConnectionError {
+    #[error("Packet error: {0}")]
+    PacketError(#[from] PacketError),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] SerializationError),
+
+    #[error("IO Error: {0}")]
+    IOError(#[from] io::Error),
+
+    #[error("Expected packet 0x{expected:02X}, got 0x{actual:02X}")]
+    UnexpectedPacket { expected: i32, actual: i32 },
+
+    #[error("Connection closed")]
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum State {
+    Handshaking,
+    Status,
+    Login,
+    Configuration,
+    Play,
+}
+
+/// Which end of the connection this is. Decides which packets are outgoing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Client,
+    Server,
+}
+
+/// Owns a stream and the framing state of one Minecraft connection.
+///
+/// The state follows Handshake, Login Acknowledged, Acknowledge Finish
+/// Configuration and Acknowledge Configuration, and Set Compression is applied
+/// right after it is sent or received. Encryption has to be enabled by hand
+/// with [`enable_encryption`](Self::enable_encryption) since only the caller
+/// knows the shared secret.
+///
+/// All of these changes take effect on the packet boundary: bytes already
+/// buffered past the current packet are decoded with the new settings.
+pub struct Connection<S> {
+    stream: S,
+    side: Side,
+    state: State,
+    protocol_version: i32,
+    codec: MinecraftCodec,
+    read_buf: BytesMut,
+    write_buf: BytesMut,
+    #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+    encrypter: Option<DefaultCipher>,
+    #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+    decrypter: Option<DefaultCipher>,
+}
+
+impl<S> Connection<S> {
+    pub fn new(stream: S, side: Side) -> Self {
+        Self {
+            stream,
+            side,
+            state: State::Handshaking,
+            protocol_version: PROTOCOL_VERSION.0,
+            codec: MinecraftCodec::new(),
+            read_buf: BytesMut::new(),
+            write_buf: BytesMut::new(),
+            #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+            encrypter: None,
+            #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+            decrypter: None,
+        }
+    }
+
+    pub fn client(stream: S) -> Self {
+        Self::new(stream, Side::Client)
+    }
+
+    pub fn server(stream: S) -> Self {
+        Self::new(stream, Side::Server)
+    }
+
+    pub fn with_protocol_version(mut self, protocol_version: i32) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.state = state;
+    }
+
+    pub fn protocol_version(&self) -> i32 {
+        self.protocol_version
+    }
+
+    pub fn set_protocol_version(&mut self, protocol_version: i32) {
+        self.protocol_version = protocol_version;
+    }
+
+    pub fn compression(&self) -> Option<i32> {
+        self.codec.compression()
+    }
+
+    /// Applies to the next packet sent and the next packet received.
+    pub fn set_compression(&mut self, threshold: Option<i32>) {
+        self.codec.set_compression(threshold);
+    }
+
+    /// Always `false` without a crypto backend feature.
+    pub fn is_encrypted(&self) -> bool {
+        #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+        return self.encrypter.is_some();
+        #[cfg(not(any(feature = "openssl", feature = "rustcrypto")))]
+        false
+    }
+
+    /// Turns on AES-128-CFB8 for everything after the last packet sent and
+    /// received. Bytes already buffered are decrypted in place.
+    #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+    pub fn enable_encryption(&mut self, key: &[u8; 16]) -> Result<(), ConnectionError> {
+        let mut decrypter = DefaultCipher::new(key, Direction::Decrypt)?;
+        decrypter.apply(&mut self.read_buf)?;
+
+        self.encrypter = Some(DefaultCipher::new(key, Direction::Encrypt)?);
+        self.decrypter = Some(decrypter);
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Returns the stream. Buffered but unread bytes are lost.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn is_serverbound(&self, sent: bool) -> bool {
+        match self.side {
+            Side::Client => sent,
+            Side::Server => !sent,
+        }
+    }
+
+    /// Updates state and compression after `packet` went through.
+    fn track(&mut self, packet: &UncompressedPacket, sent: bool) -> Result<(), ConnectionError> {
+        let id = &packet.packet_id;
+
+        if self.is_serverbound(sent) {
+            match self.state {
+                State::Handshaking if *id == Handshake::PACKET_ID => {
+                    let handshake = packet.convert::<Handshake>()?;
+                    self.state = if handshake.next_state == Handshake::STATUS {
+                        State::Status
+                    } else {
+                        State::Login
+                    };
+                }
+                State::Login if *id == LoginAcknowledged::PACKET_ID => {
+                    self.state = State::Configuration;
+                }
+                State::Configuration if *id == AcknowledgeFinishConfiguration::PACKET_ID => {
+                    self.state = State::Play;
+                }
+                State::Play if *id == AcknowledgeConfiguration::PACKET_ID => {
+                    self.state = State::Configuration;
+                }
+                _ => {}
+            }
+        } else if self.state == State::Login && *id == SetCompression::PACKET_ID {
+            let threshold = packet.convert::<SetCompression>()?.threshold.0;
+            self.set_compression(Some(threshold));
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Connection<S> {
+    pub async fn send_raw(&mut self, packet: &UncompressedPacket) -> Result<(), ConnectionError> {
+        self.write_buf.clear();
+        self.codec.encode(packet, &mut self.write_buf)?;
+
+        #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+        if let Some(encrypter) = &mut self.encrypter {
+            encrypter.apply(&mut self.write_buf)?;
+        }
+
+        self.stream.write_all(&self.write_buf).await?;
+        self.stream.flush().await?;
+
+        self.track(packet, true)
+    }
+
+    pub async fn send<P: Packet>(&mut self, packet: &P) -> Result<(), ConnectionError> {
+        let packet = packet.encode(self.protocol_version)?;
+        self.send_raw(&packet).await
+    }
+
+    /// Reads the next packet. Returns [`ConnectionError::Closed`] if the
+    /// stream ends between packets.
+    pub async fn recv_raw(&mut self) -> Result<UncompressedPacket, ConnectionError> {
+        loop {
+            if let Some(packet) = self.codec.decode(&mut self.read_buf)? {
+                self.track(&packet, false)?;
+                return Ok(packet);
+            }
+
+            let read = self.stream.read_buf(&mut self.read_buf).await?;
+            if read == 0 {
+                if self.read_buf.is_empty() {
+                    return Err(ConnectionError::Closed);
+                }
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+
+            #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+            if let Some(decrypter) = &mut self.decrypter {
+                let start = self.read_buf.len() - read;
+                decrypter.apply(&mut self.read_buf[start..])?;
+            }
+        }
+    }
+
+    /// Reads the next packet and decodes it as `P`. Fails with
+    /// [`ConnectionError::UnexpectedPacket`] if a different packet arrives.
+    pub async fn recv<P: Packet>(&mut self) -> Result<P, ConnectionError> {
+        let packet = self.recv_raw().await?;
+
+        let expected = P::packet_id(self.protocol_version).ok_or(
+            SerializationError::UnsupportedVersion(self.protocol_version),
+        )?;
+        if packet.packet_id != expected {
+            return Err(ConnectionError::UnexpectedPacket {
+                expected: expected.0,
+                actual: packet.packet_id.0,
+            });
+        }
+
+        let mut cursor = Cursor::new(&packet.payload);
+        let value = P::decode(&mut cursor, self.protocol_version)?;
+
+        let remaining = packet.payload.len() - cursor.position() as usize;
+        if remaining > 0 {
+            return Err(SerializationError::TrailingBytes(remaining).into());
+        }
+
+        Ok(value)
+    }
+}
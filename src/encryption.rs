@@ -0,0 +1,295 @@
+//! Login encryption: the RSA key exchange that hands over the AES shared
+//! secret.
+//!
+//! Server side, [`RsaKeyPair`] builds the Encryption Request and checks the
+//! Encryption Response. Client side, [`encryption_response`] answers the
+//! request with a secret from [`generate_shared_secret`]. Either way the
+//! resulting `[u8; 16]` goes to [`CFB8Stream::new`](crate::cfb8_stream::CFB8Stream::new)
+//! or `Connection::enable_encryption`.
+//!
+//! [`server_hash`] is what both sides send to the session server.
+//!
+//! Uses the same backend as [`cipher`](crate::cipher).
+
+use thiserror::Error;
+
+use crate::packets::login::{clientbound::EncryptionRequest, serverbound::EncryptionResponse};
+
+#[derive(Debug, Error)]
+pub enum EncryptionError {
+    #[cfg(feature = "openssl")]
+    #[error("OpenSSL error: {0}")]
+    OpenSsl(#[from] openssl::error::ErrorStack),
+
+    #[cfg(feature = "rustcrypto")]
+    #[error("RSA error: {0}")]
+    Rsa(#[from] rsa::Error),
+
+    #[cfg(feature = "rustcrypto")]
+    #[error("Invalid public key: {0}")]
+    PublicKey(#[from] rsa::pkcs8::spki::Error),
+
+    #[error("Shared secret must be 16 bytes, got {0}")]
+    InvalidSharedSecret(usize),
+
+    #[error("Verify token mismatch")]
+    VerifyTokenMismatch,
+}
+
+/// Key size used by vanilla servers.
+pub const RSA_BITS: usize = 1024;
+
+#[cfg(feature = "openssl")]
+mod backend {
+    use openssl::{
+        pkey::Private,
+        rsa::{Padding, Rsa},
+    };
+
+    use super::{EncryptionError, RSA_BITS};
+
+    pub type PrivateKey = Rsa<Private>;
+
+    pub fn generate() -> Result<PrivateKey, EncryptionError> {
+        Ok(Rsa::generate(RSA_BITS as u32)?)
+    }
+
+    pub fn public_key_der(key: &PrivateKey) -> Result<Vec<u8>, EncryptionError> {
+        Ok(key.public_key_to_der()?)
+    }
+
+    pub fn decrypt(key: &PrivateKey, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let mut output = vec![0; key.size() as usize];
+        let count = key.private_decrypt(data, &mut output, Padding::PKCS1)?;
+        output.truncate(count);
+        Ok(output)
+    }
+
+    pub fn encrypt(public_key_der: &[u8], data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let key = Rsa::public_key_from_der(public_key_der)?;
+        let mut output = vec![0; key.size() as usize];
+        let count = key.public_encrypt(data, &mut output, Padding::PKCS1)?;
+        output.truncate(count);
+        Ok(output)
+    }
+
+    pub fn fill_random(buf: &mut [u8]) -> Result<(), EncryptionError> {
+        Ok(openssl::rand::rand_bytes(buf)?)
+    }
+
+    pub fn sha1(parts: &[&[u8]]) -> [u8; 20] {
+        let mut hasher = openssl::sha::Sha1::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finish()
+    }
+
+    pub fn verify_sha1(
+        public_key_der: &[u8],
+        data: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, EncryptionError> {
+        use openssl::{hash::MessageDigest, pkey::PKey, sign::Verifier};
+
+        let key = PKey::public_key_from_der(public_key_der)?;
+        let mut verifier = Verifier::new(MessageDigest::sha1(), &key)?;
+        verifier.update(data)?;
+        // A malformed signature is just an invalid one
+        Ok(verifier.verify(signature).unwrap_or(false))
+    }
+}
+
+#[cfg(all(feature = "rustcrypto", not(feature = "openssl")))]
+mod backend {
+    use rand_core::{OsRng, RngCore};
+    use rsa::{
+        Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey,
+        pkcs8::{DecodePublicKey, EncodePublicKey},
+    };
+
+    use super::{EncryptionError, RSA_BITS};
+
+    pub type PrivateKey = RsaPrivateKey;
+
+    pub fn generate() -> Result<PrivateKey, EncryptionError> {
+        Ok(RsaPrivateKey::new(&mut OsRng, RSA_BITS)?)
+    }
+
+    pub fn public_key_der(key: &PrivateKey) -> Result<Vec<u8>, EncryptionError> {
+        Ok(key.to_public_key().to_public_key_der()?.into_vec())
+    }
+
+    pub fn decrypt(key: &PrivateKey, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        Ok(key.decrypt(Pkcs1v15Encrypt, data)?)
+    }
+
+    pub fn encrypt(public_key_der: &[u8], data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let key = RsaPublicKey::from_public_key_der(public_key_der)?;
+        Ok(key.encrypt(&mut OsRng, Pkcs1v15Encrypt, data)?)
+    }
+
+    pub fn fill_random(buf: &mut [u8]) -> Result<(), EncryptionError> {
+        OsRng.fill_bytes(buf);
+        Ok(())
+    }
+
+    pub fn sha1(parts: &[&[u8]]) -> [u8; 20] {
+        use sha1::{Digest, Sha1};
+
+        let mut hasher = Sha1::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+
+    pub fn verify_sha1(
+        public_key_der: &[u8],
+        data: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, EncryptionError> {
+        use rsa::Pkcs1v15Sign;
+        use sha1::Sha1;
+
+        let key = RsaPublicKey::from_public_key_der(public_key_der)?;
+        let hashed = sha1(&[data]);
+        Ok(key
+            .verify(Pkcs1v15Sign::new::<Sha1>(), &hashed, signature)
+            .is_ok())
+    }
+}
+
+/// Server-side RSA key. Generate one at startup and reuse it for every login.
+pub struct RsaKeyPair {
+    private_key: backend::PrivateKey,
+    public_key: Vec<u8>,
+}
+
+impl RsaKeyPair {
+    pub fn generate() -> Result<Self, EncryptionError> {
+        let private_key = backend::generate()?;
+        let public_key = backend::public_key_der(&private_key)?;
+
+        Ok(Self {
+            private_key,
+            public_key,
+        })
+    }
+
+    /// DER-encoded SubjectPublicKeyInfo, as sent in the Encryption Request.
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// `server_id` is left empty like on vanilla servers.
+    pub fn encryption_request(
+        &self,
+        verify_token: &[u8],
+        should_authenticate: bool,
+    ) -> EncryptionRequest {
+        EncryptionRequest {
+            server_id: String::new(),
+            public_key: self.public_key.clone(),
+            verify_token: verify_token.to_vec(),
+            should_authenticate,
+        }
+    }
+
+    /// Decrypts PKCS#1 v1.5 data encrypted with the public key.
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        backend::decrypt(&self.private_key, data)
+    }
+
+    /// Decrypts both fields, checks the verify token against the one sent in
+    /// the request and returns the shared secret.
+    pub fn decrypt_response(
+        &self,
+        response: &EncryptionResponse,
+        verify_token: &[u8],
+    ) -> Result<[u8; 16], EncryptionError> {
+        if self.decrypt(&response.verify_token)? != verify_token {
+            return Err(EncryptionError::VerifyTokenMismatch);
+        }
+
+        shared_secret_key(&self.decrypt(&response.shared_secret)?)
+    }
+}
+
+pub fn random_bytes<const N: usize>() -> Result<[u8; N], EncryptionError> {
+    let mut bytes = [0; N];
+    backend::fill_random(&mut bytes)?;
+    Ok(bytes)
+}
+
+pub fn generate_shared_secret() -> Result<[u8; 16], EncryptionError> {
+    random_bytes()
+}
+
+/// Vanilla servers use 4 random bytes.
+pub fn generate_verify_token() -> Result<[u8; 4], EncryptionError> {
+    random_bytes()
+}
+
+/// Checks that a decrypted shared secret is an AES-128 key.
+pub fn shared_secret_key(bytes: &[u8]) -> Result<[u8; 16], EncryptionError> {
+    bytes
+        .try_into()
+        .map_err(|_| EncryptionError::InvalidSharedSecret(bytes.len()))
+}
+
+/// Encrypts `data` with PKCS#1 v1.5 padding for the DER public key of an
+/// Encryption Request.
+pub fn encrypt_with_public_key(
+    public_key_der: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, EncryptionError> {
+    backend::encrypt(public_key_der, data)
+}
+
+/// Client side: encrypts the shared secret and the verify token for `request`.
+pub fn encryption_response(
+    request: &EncryptionRequest,
+    shared_secret: &[u8; 16],
+) -> Result<EncryptionResponse, EncryptionError> {
+    Ok(EncryptionResponse {
+        shared_secret: encrypt_with_public_key(&request.public_key, shared_secret)?,
+        verify_token: encrypt_with_public_key(&request.public_key, &request.verify_token)?,
+    })
+}
+
+/// Checks an RSA PKCS#1 v1.5 SHA-1 signature, as used by Yggdrasil for
+/// profile properties. Fails only if the DER public key can't be parsed.
+pub fn verify_rsa_sha1(
+    public_key_der: &[u8],
+    data: &[u8],
+    signature: &[u8],
+) -> Result<bool, EncryptionError> {
+    backend::verify_sha1(public_key_der, data, signature)
+}
+
+/// The `serverId` sent to the session server: SHA-1 over the server ID, the
+/// shared secret and the DER public key, printed as a signed (two's
+/// complement) hex number without leading zeros.
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key: &[u8]) -> String {
+    let mut digest = backend::sha1(&[server_id.as_bytes(), shared_secret, public_key]);
+
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            *byte = !*byte;
+            if carry {
+                (*byte, carry) = byte.overflowing_add(1);
+            }
+        }
+    }
+
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    let hex = hex.trim_start_matches('0');
+    match (negative, hex.is_empty()) {
+        (_, true) => "0".to_string(),
+        (true, false) => format!("-{hex}"),
+        (false, false) => hex.to_string(),
+    }
+}
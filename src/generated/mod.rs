@@ -0,0 +1,16 @@
+//! Packet definitions generated from minecraft-data `protocol.json` files by
+//! `minecraft_protocol_codegen`. Each protocol version is behind a cargo
+//! feature of the same name.
+//!
+//! To regenerate a version:
+//!
+//! ```sh
+//! cargo run -p minecraft_protocol_codegen -- \
+//!     minecraft_protocol_codegen/data/pc/1.21/protocol.json src/generated/v1_21.rs
+//! ```
+
+/// Handshaking, status and login packets only, generated from a partial
+/// 1.21 `protocol.json`. Configuration and play need the full upstream file,
+/// see the README.
+#[cfg(feature = "v1_21")]
+pub mod v1_21;
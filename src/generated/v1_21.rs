@@ -0,0 +1,136 @@
+// @generated by minecraft_protocol_codegen from minecraft_protocol_codegen/data/pc/1.21/protocol.json. Do not edit.
+
+pub mod handshaking {
+    pub mod serverbound {
+        use crate::{Packet, varint::VarInt};
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x00)]
+        pub struct SetProtocol {
+            pub protocol_version: VarInt,
+            pub server_host: String,
+            pub server_port: u16,
+            pub next_state: VarInt,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0xFE)]
+        pub struct LegacyServerListPing {
+            pub payload: u8,
+        }
+    }
+
+    pub mod clientbound {}
+}
+
+pub mod status {
+    pub mod serverbound {
+        use crate::Packet;
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x00)]
+        pub struct PingStart;
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x01)]
+        pub struct Ping {
+            pub time: i64,
+        }
+    }
+
+    pub mod clientbound {
+        use crate::Packet;
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x00)]
+        pub struct ServerInfo {
+            pub response: String,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x01)]
+        pub struct Ping {
+            pub time: i64,
+        }
+    }
+}
+
+pub mod login {
+    pub mod serverbound {
+        use crate::{Packet, ser::RestBuffer, varint::VarInt};
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x00)]
+        pub struct LoginStart {
+            pub username: String,
+            pub player_uuid: u128,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x01)]
+        pub struct EncryptionBegin {
+            pub shared_secret: Vec<u8>,
+            pub verify_token: Vec<u8>,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x02)]
+        pub struct LoginPluginResponse {
+            pub message_id: VarInt,
+            pub data: Option<RestBuffer>,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x03)]
+        pub struct LoginAcknowledged;
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x04)]
+        pub struct CookieResponse {
+            pub cookie: String,
+            pub value: Option<Vec<u8>>,
+        }
+    }
+
+    pub mod clientbound {
+        // Skipped, unsupported field types:
+        // - 0x02 success (container)
+
+        use crate::{Packet, ser::RestBuffer, varint::VarInt};
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x00)]
+        pub struct Disconnect {
+            pub reason: String,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x01)]
+        pub struct EncryptionBegin {
+            pub server_id: String,
+            pub public_key: Vec<u8>,
+            pub verify_token: Vec<u8>,
+            pub should_authenticate: bool,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x03)]
+        pub struct Compress {
+            pub threshold: VarInt,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x04)]
+        pub struct LoginPluginRequest {
+            pub message_id: VarInt,
+            pub channel: String,
+            pub data: RestBuffer,
+        }
+
+        #[derive(Packet, Debug, Clone, PartialEq)]
+        #[packet(0x05)]
+        pub struct CookieRequest {
+            pub cookie: String,
+        }
+    }
+}
@@ -1,7 +1,32 @@
+// Allows #[derive(Packet)] inside this crate
+extern crate self as minecraft_protocol;
+
 pub use minecraft_protocol_derive::*;
 
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
 pub mod cfb8_stream;
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+pub mod cipher;
+#[cfg(feature = "tokio")]
+pub mod codec;
+#[cfg(feature = "tokio")]
+pub mod connection;
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+pub mod encryption;
+pub mod generated;
+#[cfg(all(feature = "tokio", any(feature = "openssl", feature = "rustcrypto")))]
+pub mod login;
+pub mod nbt;
 pub mod num;
 pub mod packet;
+pub mod packets;
+pub mod profile;
+#[cfg(feature = "tokio")]
+pub mod query;
+#[cfg(feature = "tokio")]
+pub mod rcon;
 pub mod ser;
+pub mod session;
+pub mod status;
 pub mod varint;
+pub mod version;
@@ -0,0 +1,156 @@
+use std::future::Future;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use super::LoginError;
+use crate::{
+    connection::Connection,
+    encryption::{encryption_response, generate_shared_secret, server_hash},
+    packets::{
+        handshake::serverbound::Handshake,
+        login::{
+            clientbound::{
+                CookieRequest, Disconnect, EncryptionRequest, LoginPluginRequest, LoginSuccess,
+                SetCompression,
+            },
+            serverbound::{CookieResponse, LoginAcknowledged, LoginPluginResponse, LoginStart},
+        },
+    },
+    profile::GameProfile,
+    session::{HttpTransport, SessionClient},
+    varint::VarInt,
+};
+
+/// How the client proves who it is when the server asks for authentication.
+pub trait ClientAuth {
+    /// Called with the server hash before Encryption Response is sent.
+    fn join(
+        &self,
+        profile: &GameProfile,
+        server_hash: &str,
+    ) -> impl Future<Output = Result<(), LoginError>> + Send;
+}
+
+/// For offline-mode servers. Logging in to an online-mode server fails with
+/// [`LoginError::AuthenticationRequired`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineAuth;
+
+/// Joins through the session server with a Minecraft access token.
+pub struct SessionAuth<'a, T> {
+    pub session: &'a SessionClient<T>,
+    pub access_token: &'a str,
+}
+
+impl ClientAuth for OfflineAuth {
+    async fn join(&self, _profile: &GameProfile, _server_hash: &str) -> Result<(), LoginError> {
+        Err(LoginError::AuthenticationRequired)
+    }
+}
+
+impl<T: HttpTransport + Sync> ClientAuth for SessionAuth<'_, T> {
+    async fn join(&self, profile: &GameProfile, server_hash: &str) -> Result<(), LoginError> {
+        Ok(self
+            .session
+            .join(self.access_token, profile.id, server_hash)
+            .await?)
+    }
+}
+
+/// Logs `profile` in, from Handshake to Login Acknowledged.
+///
+/// Encryption and compression are enabled on `connection` as the server asks
+/// for them. Login Plugin and Cookie requests are answered as not
+/// understood. Returns the profile from Login Success, with `connection` in
+/// the Configuration state.
+///
+/// `server_address` and `server_port` go into the Handshake, which this sends
+/// itself, so `connection` must be fresh.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), minecraft_protocol::login::LoginError> {
+/// use minecraft_protocol::{
+///     connection::Connection,
+///     login::{OfflineAuth, client_login},
+///     profile::GameProfile,
+/// };
+///
+/// let stream = tokio::net::TcpStream::connect("example.com:25565").await.unwrap();
+/// let mut connection = Connection::client(stream);
+/// let profile = GameProfile::new(0, "Steve");
+/// let profile = client_login(&mut connection, "example.com", 25565, &profile, &OfflineAuth).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn client_login<S, A>(
+    connection: &mut Connection<S>,
+    server_address: &str,
+    server_port: u16,
+    profile: &GameProfile,
+    auth: &A,
+) -> Result<GameProfile, LoginError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    A: ClientAuth,
+{
+    connection
+        .send(&Handshake {
+            protocol_version: VarInt(connection.protocol_version()),
+            server_address: server_address.to_string(),
+            server_port,
+            next_state: Handshake::LOGIN,
+        })
+        .await?;
+    connection
+        .send(&LoginStart {
+            name: profile.name.clone(),
+            uuid: profile.id,
+        })
+        .await?;
+
+    loop {
+        // Set Compression is applied by the connection itself
+        let packet = connection.recv_raw().await?;
+        let id = &packet.packet_id;
+
+        if *id == Disconnect::PACKET_ID {
+            let disconnect: Disconnect = packet.convert_exact()?;
+            return Err(LoginError::disconnected(&disconnect.reason));
+        } else if *id == EncryptionRequest::PACKET_ID {
+            let request: EncryptionRequest = packet.convert_exact()?;
+            let shared_secret = generate_shared_secret()?;
+
+            if request.should_authenticate {
+                let hash = server_hash(&request.server_id, &shared_secret, &request.public_key);
+                auth.join(profile, &hash).await?;
+            }
+
+            connection
+                .send(&encryption_response(&request, &shared_secret)?)
+                .await?;
+            connection.enable_encryption(&shared_secret)?;
+        } else if *id == LoginPluginRequest::PACKET_ID {
+            let request: LoginPluginRequest = packet.convert_exact()?;
+            connection
+                .send(&LoginPluginResponse {
+                    message_id: request.message_id,
+                    data: None,
+                })
+                .await?;
+        } else if *id == CookieRequest::PACKET_ID {
+            let request: CookieRequest = packet.convert_exact()?;
+            connection
+                .send(&CookieResponse {
+                    key: request.key,
+                    payload: None,
+                })
+                .await?;
+        } else if *id == LoginSuccess::PACKET_ID {
+            let success: LoginSuccess = packet.convert_exact()?;
+            connection.send(&LoginAcknowledged).await?;
+            return Ok(GameProfile::from(&success));
+        } else if *id != SetCompression::PACKET_ID {
+            return Err(LoginError::UnexpectedPacket(id.0));
+        }
+    }
+}
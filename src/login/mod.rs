@@ -0,0 +1,60 @@
+//! The Login state, from Handshake to Login Acknowledged.
+//!
+//! Both drivers run on a [`Connection`](crate::connection::Connection)
+//! rather than a bare `CFB8Stream` and `RawPacket`, so encryption,
+//! compression and the state switch to Configuration are applied to it in
+//! place. The caller keeps the connection and carries on with it.
+
+mod client;
+mod server;
+
+use thiserror::Error;
+
+use crate::{
+    connection::ConnectionError, encryption::EncryptionError, ser::SerializationError,
+    session::SessionError, status::plain_text,
+};
+
+pub use client::{ClientAuth, OfflineAuth, SessionAuth, client_login};
+pub use server::{
+    Authenticator, LoginAttempt, OfflineAuthenticator, ServerLoginOptions, SessionAuthenticator,
+    WithCheck, server_login,
+};
+
+#[derive(Debug, Error)]
+pub enum LoginError {
+    #[error("Connection error: {0}")]
+    Connection(#[from] ConnectionError),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] SerializationError),
+
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
+
+    #[error("Session server error: {0}")]
+    Session(#[from] SessionError),
+
+    /// Text component sent with Disconnect.
+    #[error("Disconnected: {}", plain_text(.0))]
+    Disconnected(serde_json::Value),
+
+    #[error("Server requires authentication")]
+    AuthenticationRequired,
+
+    #[error("Session verification needs encryption, but no key pair was given")]
+    MissingKeyPair,
+
+    #[error("Unexpected packet 0x{0:02X} during login")]
+    UnexpectedPacket(i32),
+}
+
+impl LoginError {
+    /// Reads the reason of a Disconnect packet. Anything that isn't JSON is
+    /// kept as a plain string.
+    pub(crate) fn disconnected(reason: &str) -> Self {
+        let reason = serde_json::from_str(reason)
+            .unwrap_or_else(|_| serde_json::Value::String(reason.to_string()));
+        Self::Disconnected(reason)
+    }
+}
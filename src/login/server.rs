@@ -0,0 +1,206 @@
+use std::future::Future;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use super::LoginError;
+use crate::{
+    connection::Connection,
+    encryption::{RsaKeyPair, generate_verify_token, server_hash},
+    packets::login::{
+        clientbound::{Disconnect, SetCompression},
+        serverbound::{EncryptionResponse, LoginAcknowledged, LoginStart},
+    },
+    profile::GameProfile,
+    session::{HttpTransport, SessionClient},
+    varint::VarInt,
+};
+
+/// What the client sent, handed to the [`Authenticator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginAttempt {
+    pub username: String,
+    /// Chosen by the client, only trustworthy after session verification.
+    pub uuid: u128,
+    /// Set when encryption ran, for `hasJoined`.
+    pub server_hash: Option<String>,
+}
+
+/// Decides who the client is.
+///
+/// Returning [`LoginError::Disconnected`] kicks the client with that reason.
+/// Any other error kicks it with vanilla's "Failed to verify username".
+pub trait Authenticator {
+    /// Whether [`LoginAttempt::server_hash`] is needed, which makes the
+    /// client join through the session server first.
+    fn needs_server_hash(&self) -> bool {
+        false
+    }
+
+    fn authenticate(
+        &self,
+        attempt: &LoginAttempt,
+    ) -> impl Future<Output = Result<GameProfile, LoginError>> + Send;
+
+    /// Runs `check` on each authenticated profile, e.g. a whitelist or ban
+    /// list. An `Err` is the kick reason, a text component.
+    fn with_check<F>(self, check: F) -> WithCheck<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&GameProfile) -> Result<(), serde_json::Value> + Sync,
+    {
+        WithCheck { inner: self, check }
+    }
+}
+
+/// Trusts the username and derives the UUID from it like offline-mode
+/// servers, see [`GameProfile::offline`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineAuthenticator;
+
+/// Verifies the player with `hasJoined`, like online-mode servers.
+pub struct SessionAuthenticator<T> {
+    pub session: SessionClient<T>,
+}
+
+/// See [`Authenticator::with_check`].
+pub struct WithCheck<A, F> {
+    inner: A,
+    check: F,
+}
+
+impl Authenticator for OfflineAuthenticator {
+    async fn authenticate(&self, attempt: &LoginAttempt) -> Result<GameProfile, LoginError> {
+        Ok(GameProfile::offline(attempt.username.clone()))
+    }
+}
+
+impl<T: HttpTransport + Sync> Authenticator for SessionAuthenticator<T> {
+    fn needs_server_hash(&self) -> bool {
+        true
+    }
+
+    async fn authenticate(&self, attempt: &LoginAttempt) -> Result<GameProfile, LoginError> {
+        let server_hash = attempt
+            .server_hash
+            .as_deref()
+            .ok_or(LoginError::MissingKeyPair)?;
+        Ok(self
+            .session
+            .has_joined(&attempt.username, server_hash, None)
+            .await?)
+    }
+}
+
+impl<A, F> Authenticator for WithCheck<A, F>
+where
+    A: Authenticator + Sync,
+    F: Fn(&GameProfile) -> Result<(), serde_json::Value> + Sync,
+{
+    fn needs_server_hash(&self) -> bool {
+        self.inner.needs_server_hash()
+    }
+
+    async fn authenticate(&self, attempt: &LoginAttempt) -> Result<GameProfile, LoginError> {
+        let profile = self.inner.authenticate(attempt).await?;
+        (self.check)(&profile).map_err(LoginError::Disconnected)?;
+        Ok(profile)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ServerLoginOptions<'a> {
+    /// Encrypts the connection when set. Required by authenticators that
+    /// need a server hash.
+    pub key_pair: Option<&'a RsaKeyPair>,
+    /// Sent with Set Compression before Login Success.
+    pub compression_threshold: Option<i32>,
+}
+
+/// Logs a client in, from Login Start to Login Acknowledged.
+///
+/// `connection` must be in the Login state, i.e. the Handshake was read.
+/// Returns the authenticated profile, with `connection` encrypted and
+/// compressed as configured and in the Configuration state.
+///
+/// ```no_run
+/// # async fn example(socket: tokio::net::TcpStream) -> Result<(), minecraft_protocol::login::LoginError> {
+/// use minecraft_protocol::{
+///     connection::Connection,
+///     login::{Authenticator, OfflineAuthenticator, ServerLoginOptions, server_login},
+///     packets::handshake::serverbound::Handshake,
+/// };
+///
+/// let mut connection = Connection::server(socket);
+/// let _handshake: Handshake = connection.recv().await?;
+///
+/// let auth = OfflineAuthenticator.with_check(|profile| match profile.name.as_str() {
+///     "Herobrine" => Err("You are banned".into()),
+///     _ => Ok(()),
+/// });
+/// let options = ServerLoginOptions {
+///     compression_threshold: Some(256),
+///     ..Default::default()
+/// };
+/// let profile = server_login(&mut connection, &options, &auth).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn server_login<S, A>(
+    connection: &mut Connection<S>,
+    options: &ServerLoginOptions<'_>,
+    authenticator: &A,
+) -> Result<GameProfile, LoginError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    A: Authenticator,
+{
+    let start: LoginStart = connection.recv().await?;
+    let mut attempt = LoginAttempt {
+        username: start.name,
+        uuid: start.uuid,
+        server_hash: None,
+    };
+
+    if authenticator.needs_server_hash() && options.key_pair.is_none() {
+        return Err(LoginError::MissingKeyPair);
+    }
+    if let Some(key_pair) = options.key_pair {
+        let verify_token = generate_verify_token()?;
+        connection
+            .send(&key_pair.encryption_request(&verify_token, authenticator.needs_server_hash()))
+            .await?;
+
+        let response: EncryptionResponse = connection.recv().await?;
+        let shared_secret = key_pair.decrypt_response(&response, &verify_token)?;
+        connection.enable_encryption(&shared_secret)?;
+        attempt.server_hash = Some(server_hash("", &shared_secret, key_pair.public_key()));
+    }
+
+    let profile = match authenticator.authenticate(&attempt).await {
+        Ok(profile) => profile,
+        Err(error) => {
+            let reason = match &error {
+                LoginError::Disconnected(reason) => reason.clone(),
+                _ => serde_json::json!({"translate": "multiplayer.disconnect.unverified_username"}),
+            };
+            connection
+                .send(&Disconnect {
+                    reason: reason.to_string(),
+                })
+                .await?;
+            return Err(error);
+        }
+    };
+
+    if let Some(threshold) = options.compression_threshold {
+        connection
+            .send(&SetCompression {
+                threshold: VarInt(threshold),
+            })
+            .await?;
+    }
+    connection.send(&profile.login_success()).await?;
+    connection.recv::<LoginAcknowledged>().await?;
+
+    Ok(profile)
+}
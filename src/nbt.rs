@@ -0,0 +1,171 @@
+use std::io::{self, Read, Write};
+
+use crate::ser::{Deserialize, SerializationError, Serialize};
+
+const TAG_END: u8 = 0;
+const TAG_BYTE: u8 = 1;
+const TAG_SHORT: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_LONG: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_DOUBLE: u8 = 6;
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_STRING: u8 = 8;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_INT_ARRAY: u8 = 11;
+const TAG_LONG_ARRAY: u8 = 12;
+
+/// Same limit as the vanilla NBT reader.
+const MAX_DEPTH: usize = 512;
+
+/// Network NBT (nameless root tag, 1.20.2+) kept as raw bytes.
+///
+/// Used for text components, registry data and item components. Decoding walks
+/// the tag tree only to find where it ends, the bytes are stored unchanged.
+/// An empty tag (a single `TAG_End` byte) stands for "no value" in optional
+/// NBT fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nbt(pub Vec<u8>);
+
+impl Nbt {
+    /// `TAG_End` on its own.
+    pub fn empty() -> Self {
+        Self(vec![TAG_END])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.first().is_none_or(|&tag| tag == TAG_END)
+    }
+
+    /// Root `TAG_String`, the form plain text components take on the wire.
+    /// Fails if `value` is longer than the `u16` length prefix allows.
+    pub fn string(value: &str) -> Result<Self, SerializationError> {
+        let length = u16::try_from(value.len())
+            .map_err(|_| SerializationError::InvalidValue("NBT string too long"))?;
+
+        let mut data = vec![TAG_STRING];
+        data.extend_from_slice(&length.to_be_bytes());
+        data.extend_from_slice(value.as_bytes());
+        Ok(Self(data))
+    }
+}
+
+impl Default for Nbt {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Serialize for Nbt {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        if self.0.is_empty() {
+            return Ok(writer.write_all(&[TAG_END])?);
+        }
+
+        Ok(writer.write_all(&self.0)?)
+    }
+}
+
+impl Deserialize for Nbt {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        let mut data = Vec::new();
+        let tag = copy(reader, 1, &mut data)?[0];
+
+        if tag != TAG_END {
+            copy_payload(reader, tag, &mut data, 0)?;
+        }
+
+        Ok(Self(data))
+    }
+}
+
+/// Reads exactly `len` bytes into `out` and returns them.
+fn copy<'a, R: Read>(
+    reader: &mut R,
+    len: usize,
+    out: &'a mut Vec<u8>,
+) -> Result<&'a [u8], SerializationError> {
+    let start = out.len();
+    // take() instead of resize() so a bogus length can't allocate gigabytes up front
+    if reader.take(len as u64).read_to_end(out)? != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(&out[start..])
+}
+
+fn copy_len<R: Read>(
+    reader: &mut R,
+    out: &mut Vec<u8>,
+    element_size: usize,
+) -> Result<usize, SerializationError> {
+    let len = i32::from_be_bytes(copy(reader, 4, out)?.try_into().unwrap());
+    if len < 0 {
+        return Err(SerializationError::InvalidNbt("negative length"));
+    }
+    Ok(len as usize * element_size)
+}
+
+fn copy_payload<R: Read>(
+    reader: &mut R,
+    tag: u8,
+    out: &mut Vec<u8>,
+    depth: usize,
+) -> Result<(), SerializationError> {
+    if depth > MAX_DEPTH {
+        return Err(SerializationError::InvalidNbt("nesting too deep"));
+    }
+
+    match tag {
+        TAG_BYTE => {
+            copy(reader, 1, out)?;
+        }
+        TAG_SHORT => {
+            copy(reader, 2, out)?;
+        }
+        TAG_INT | TAG_FLOAT => {
+            copy(reader, 4, out)?;
+        }
+        TAG_LONG | TAG_DOUBLE => {
+            copy(reader, 8, out)?;
+        }
+        TAG_BYTE_ARRAY => {
+            let len = copy_len(reader, out, 1)?;
+            copy(reader, len, out)?;
+        }
+        TAG_INT_ARRAY => {
+            let len = copy_len(reader, out, 4)?;
+            copy(reader, len, out)?;
+        }
+        TAG_LONG_ARRAY => {
+            let len = copy_len(reader, out, 8)?;
+            copy(reader, len, out)?;
+        }
+        TAG_STRING => {
+            let len = u16::from_be_bytes(copy(reader, 2, out)?.try_into().unwrap());
+            copy(reader, len as usize, out)?;
+        }
+        TAG_LIST => {
+            let element = copy(reader, 1, out)?[0];
+            let len = copy_len(reader, out, 1)?;
+            if element == TAG_END && len > 0 {
+                return Err(SerializationError::InvalidNbt("non-empty list of TAG_End"));
+            }
+            for _ in 0..len {
+                copy_payload(reader, element, out, depth + 1)?;
+            }
+        }
+        TAG_COMPOUND => loop {
+            let child = copy(reader, 1, out)?[0];
+            if child == TAG_END {
+                break;
+            }
+            let name_len = u16::from_be_bytes(copy(reader, 2, out)?.try_into().unwrap());
+            copy(reader, name_len as usize, out)?;
+            copy_payload(reader, child, out, depth + 1)?;
+        },
+        _ => return Err(SerializationError::InvalidNbt("unknown tag type")),
+    }
+
+    Ok(())
+}
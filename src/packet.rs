@@ -1,6 +1,6 @@
-use std::io::{self, Cursor};
+use flate2::{Compression, write::ZlibEncoder};
+use std::io::{self, Cursor, Write};
 use thiserror::Error;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::{
     ser::{Deserialize, SerializationError},
@@ -14,6 +14,37 @@ pub enum PacketError {
 
     #[error("IO Error: {0}")]
     IOError(#[from] io::Error),
+
+    #[error("Frame of {0} bytes exceeds the limit of {1} bytes")]
+    FrameTooLarge(usize, usize),
+
+    #[error("Invalid uncompressed data length {0}")]
+    InvalidDataLength(i32),
+}
+
+/// Checks `since <= protocol_version <= until`, used by `#[derive(Packet)]`
+/// for `#[packet(since = .., until = ..)]` attributes.
+#[doc(hidden)]
+pub fn in_version_range(protocol_version: i32, since: Option<i32>, until: Option<i32>) -> bool {
+    since.is_none_or(|since| protocol_version >= since)
+        && until.is_none_or(|until| protocol_version <= until)
+}
+
+/// Version-aware packet encoding, implemented by `#[derive(Packet)]`.
+///
+/// Lets code such as [`Connection`](crate::connection::Connection) send and
+/// receive any packet type without knowing whether it is versioned.
+pub trait Packet: Sized {
+    /// Packet ID in `protocol_version`, `None` if the packet does not exist there.
+    fn packet_id(protocol_version: i32) -> Option<VarInt>;
+
+    fn encode(&self, protocol_version: i32) -> Result<UncompressedPacket, SerializationError>;
+
+    /// Reads the payload, without the packet ID.
+    fn decode<R: io::Read + Unpin>(
+        reader: &mut R,
+        protocol_version: i32,
+    ) -> Result<Self, SerializationError>;
 }
 
 #[derive(Debug, Clone)]
@@ -21,36 +52,101 @@ pub struct CompressedPacket {
     pub data: Vec<u8>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UncompressedPacket {
     pub packet_id: VarInt,
     pub payload: Vec<u8>,
 }
 
+/// Zeroed buffer for a frame of length `len`.
+fn frame_buffer(len: VarInt) -> Result<Vec<u8>, PacketError> {
+    if len.0 < 0 {
+        return Err(VarIntError::NegativeValue.into());
+    }
+
+    Ok(vec![0; len.0 as usize])
+}
+
 #[derive(Debug, Clone)]
 pub struct RawPacket {
     pub data: Vec<u8>,
 }
 
 impl RawPacket {
-    pub async fn read<R: AsyncReadExt + Unpin>(reader: &mut R) -> Result<Self, PacketError> {
-        let len = VarInt::read(reader).await?;
-        if len.0 < 0 {
-            return Err(VarIntError::NegativeValue.into());
-        }
+    #[cfg(feature = "tokio")]
+    pub async fn read<R: tokio::io::AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<Self, PacketError> {
+        use tokio::io::AsyncReadExt;
+
+        let mut buf = frame_buffer(VarInt::read(reader).await?)?;
+        reader.read_exact(&mut buf).await?;
+
+        Ok(Self { data: buf })
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn write<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), PacketError> {
+        use tokio::io::AsyncWriteExt;
+
+        writer.write_all(&self.framed()?).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    /// [`read`](Self::read) for `futures::io::AsyncRead`.
+    #[cfg(feature = "futures-io")]
+    pub async fn read_futures<R: futures_io::AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<Self, PacketError> {
+        use futures_util::AsyncReadExt;
 
-        let mut buf = vec![0; len.0 as usize];
+        let mut buf = frame_buffer(VarInt::read_futures(reader).await?)?;
         reader.read_exact(&mut buf).await?;
 
         Ok(Self { data: buf })
     }
 
-    pub async fn write<W: AsyncWriteExt + Unpin>(&self, writer: &mut W) -> Result<(), PacketError> {
-        VarInt(self.data.len() as i32).write(writer).await?;
-        writer.write_all(&self.data).await?;
+    /// [`write`](Self::write) for `futures::io::AsyncWrite`.
+    #[cfg(feature = "futures-io")]
+    pub async fn write_futures<W: futures_io::AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), PacketError> {
+        use futures_util::AsyncWriteExt;
+
+        writer.write_all(&self.framed()?).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    /// Blocking counterpart of [`read`](Self::read).
+    pub fn read_sync<R: io::Read + Unpin>(reader: &mut R) -> Result<Self, PacketError> {
+        let mut buf = frame_buffer(VarInt::read_sync(reader)?)?;
+        reader.read_exact(&mut buf)?;
+
+        Ok(Self { data: buf })
+    }
+
+    /// Blocking counterpart of [`write`](Self::write).
+    pub fn write_sync<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), PacketError> {
+        writer.write_all(&self.framed()?)?;
+        writer.flush()?;
         Ok(())
     }
 
+    /// Length prefix followed by the data, written in one go so the frame
+    /// isn't split across writes.
+    fn framed(&self) -> Result<Vec<u8>, PacketError> {
+        let mut buf = Vec::with_capacity(self.data.len() + 5);
+        VarInt(self.data.len() as i32).write_sync(&mut buf)?;
+        buf.extend_from_slice(&self.data);
+        Ok(buf)
+    }
+
     pub fn as_uncompressed(&self) -> Result<UncompressedPacket, PacketError> {
         let mut cursor = Cursor::new(&self.data);
         let packet_id = VarInt::read_sync(&mut cursor)?;
@@ -64,7 +160,7 @@ impl RawPacket {
         &self,
         threshold: Option<i32>,
     ) -> Result<Option<UncompressedPacket>, PacketError> {
-        if let Some(_) = threshold {
+        if threshold.is_some() {
             let mut cursor = Cursor::new(&self.data);
             let data_length = VarInt::read_sync(&mut cursor)?;
 
@@ -87,7 +183,7 @@ impl UncompressedPacket {
     pub fn to_raw_packet(&self) -> Result<RawPacket, PacketError> {
         let mut buf = Vec::new();
         self.packet_id.write_sync(&mut buf)?;
-        std::io::Write::write_all(&mut buf, &self.payload)?;
+        buf.write_all(&self.payload)?;
         Ok(RawPacket { data: buf })
     }
 
@@ -95,11 +191,32 @@ impl UncompressedPacket {
         T::deserialize(&mut Cursor::new(&self.payload))
     }
 
+    /// Like [`convert`](Self::convert), but fails if `T` leaves part of the payload unread.
+    pub fn convert_exact<T: Deserialize>(&self) -> Result<T, SerializationError> {
+        let mut cursor = Cursor::new(&self.payload);
+        let value = T::deserialize(&mut cursor)?;
+
+        let remaining = self.payload.len() - cursor.position() as usize;
+        if remaining > 0 {
+            return Err(SerializationError::TrailingBytes(remaining));
+        }
+
+        Ok(value)
+    }
+
     pub fn compress(&self, threshold: i32) -> Result<CompressedPacket, PacketError> {
         let raw_packet = self.to_raw_packet()?;
 
         if raw_packet.data.len() >= threshold as usize {
-            todo!("Implement compression");
+            let mut data = Vec::new();
+            VarInt(raw_packet.data.len() as i32).write_sync(&mut data)?;
+
+            let mut encoder = ZlibEncoder::new(data, Compression::default());
+            encoder.write_all(&raw_packet.data)?;
+
+            Ok(CompressedPacket {
+                data: encoder.finish()?,
+            })
         } else {
             let mut data = Vec::new();
             // Prepend VarInt(0) indicating uncompressed data
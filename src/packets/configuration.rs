@@ -0,0 +1,281 @@
+use crate::{Deserialize, Serialize};
+
+/// Data pack advertised during Known Packs negotiation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KnownPack {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+}
+
+impl KnownPack {
+    /// The vanilla `minecraft:core` pack for `version`, e.g. `"1.21"`.
+    pub fn core(version: &str) -> Self {
+        Self {
+            namespace: "minecraft".to_string(),
+            id: "core".to_string(),
+            version: version.to_string(),
+        }
+    }
+}
+
+pub mod serverbound {
+    use crate::{
+        Packet,
+        ser::{PrefixedArray, RestBuffer},
+        varint::VarInt,
+    };
+
+    use super::KnownPack;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x00)]
+    pub struct ClientInformation {
+        /// e.g. `en_us`
+        pub locale: String,
+        pub view_distance: i8,
+        /// 0: enabled, 1: commands only, 2: hidden
+        pub chat_mode: VarInt,
+        pub chat_colors: bool,
+        /// Bit mask of visible skin layers
+        pub displayed_skin_parts: u8,
+        /// 0: left, 1: right
+        pub main_hand: VarInt,
+        pub enable_text_filtering: bool,
+        pub allow_server_listings: bool,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x01)]
+    pub struct CookieResponse {
+        pub key: String,
+        pub payload: Option<Vec<u8>>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x02)]
+    pub struct PluginMessage {
+        pub channel: String,
+        pub data: RestBuffer,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x03)]
+    pub struct AcknowledgeFinishConfiguration;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x04)]
+    pub struct KeepAlive {
+        pub keep_alive_id: i64,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x05)]
+    pub struct Pong {
+        pub id: i32,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x06)]
+    pub struct ResourcePackResponse {
+        pub uuid: u128,
+        /// 0: loaded, 1: declined, 2: failed download, 3: accepted, 4: downloaded,
+        /// 5: invalid URL, 6: failed to reload, 7: discarded
+        pub result: VarInt,
+    }
+
+    /// Packs from [`super::clientbound::KnownPacks`] the client also has.
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x07)]
+    pub struct KnownPacks {
+        pub known_packs: PrefixedArray<KnownPack>,
+    }
+}
+
+pub mod clientbound {
+    use std::io::{Read, Write};
+
+    use crate::{
+        Deserialize, Packet, Serialize,
+        nbt::Nbt,
+        ser::{self, PrefixedArray, RestBuffer, SerializationError},
+        varint::VarInt,
+    };
+
+    use super::KnownPack;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x00)]
+    pub struct CookieRequest {
+        pub key: String,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x01)]
+    pub struct PluginMessage {
+        pub channel: String,
+        pub data: RestBuffer,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x02)]
+    pub struct Disconnect {
+        /// Text component
+        pub reason: Nbt,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x03)]
+    pub struct FinishConfiguration;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x04)]
+    pub struct KeepAlive {
+        pub keep_alive_id: i64,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x05)]
+    pub struct Ping {
+        pub id: i32,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x06)]
+    pub struct ResetChat;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x07)]
+    pub struct RegistryData {
+        pub registry_id: String,
+        pub entries: PrefixedArray<RegistryEntry>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct RegistryEntry {
+        pub entry_id: String,
+        /// `None` if the client should take the entry from a known pack
+        pub data: Option<Nbt>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x08)]
+    pub struct RemoveResourcePack {
+        /// `None` removes all resource packs
+        pub uuid: Option<u128>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x09)]
+    pub struct AddResourcePack {
+        pub uuid: u128,
+        pub url: String,
+        /// Hex SHA-1 of the pack, may be empty
+        pub hash: String,
+        pub forced: bool,
+        pub prompt_message: Option<Nbt>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x0A)]
+    pub struct StoreCookie {
+        pub key: String,
+        pub payload: Vec<u8>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x0B)]
+    pub struct Transfer {
+        pub host: String,
+        pub port: VarInt,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x0C)]
+    pub struct FeatureFlags {
+        pub feature_flags: PrefixedArray<String>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x0D)]
+    pub struct UpdateTags {
+        pub registries: PrefixedArray<RegistryTags>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct RegistryTags {
+        pub registry: String,
+        pub tags: PrefixedArray<Tag>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct Tag {
+        pub name: String,
+        /// Numeric IDs of the registry entries
+        pub entries: PrefixedArray<VarInt>,
+    }
+
+    /// Packs the server would like to skip sending registry data for.
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x0E)]
+    pub struct KnownPacks {
+        pub known_packs: PrefixedArray<KnownPack>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x0F)]
+    pub struct CustomReportDetails {
+        pub details: PrefixedArray<ReportDetail>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct ReportDetail {
+        pub title: String,
+        pub description: String,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x10)]
+    pub struct ServerLinks {
+        pub links: PrefixedArray<ServerLink>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct ServerLink {
+        pub label: ServerLinkLabel,
+        pub url: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ServerLinkLabel {
+        /// 0: bug report, 1: community guidelines, 2: support, 3: status,
+        /// 4: feedback, 5: community, 6: website, 7: forums, 8: news, 9: announcements
+        BuiltIn(VarInt),
+        /// Text component
+        Custom(Nbt),
+    }
+
+    impl ser::Serialize for ServerLinkLabel {
+        fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+            match self {
+                ServerLinkLabel::BuiltIn(kind) => {
+                    ser::Serialize::serialize(&true, writer)?;
+                    ser::Serialize::serialize(kind, writer)
+                }
+                ServerLinkLabel::Custom(text) => {
+                    ser::Serialize::serialize(&false, writer)?;
+                    ser::Serialize::serialize(text, writer)
+                }
+            }
+        }
+    }
+
+    impl ser::Deserialize for ServerLinkLabel {
+        fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+            match <bool as ser::Deserialize>::deserialize(reader)? {
+                true => Ok(ServerLinkLabel::BuiltIn(VarInt::deserialize(reader)?)),
+                false => Ok(ServerLinkLabel::Custom(Nbt::deserialize(reader)?)),
+            }
+        }
+    }
+}
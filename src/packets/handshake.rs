@@ -0,0 +1,19 @@
+pub mod serverbound {
+    use crate::{Packet, varint::VarInt};
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x00)]
+    pub struct Handshake {
+        pub protocol_version: VarInt,
+        pub server_address: String,
+        pub server_port: u16,
+        /// [`Handshake::STATUS`], [`Handshake::LOGIN`] or [`Handshake::TRANSFER`]
+        pub next_state: VarInt,
+    }
+
+    impl Handshake {
+        pub const STATUS: VarInt = VarInt(1);
+        pub const LOGIN: VarInt = VarInt(2);
+        pub const TRANSFER: VarInt = VarInt(3);
+    }
+}
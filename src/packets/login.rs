@@ -0,0 +1,106 @@
+pub mod serverbound {
+    use crate::{Packet, ser::RestBuffer, varint::VarInt};
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x00)]
+    pub struct LoginStart {
+        pub name: String,
+        pub uuid: u128,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x01)]
+    pub struct EncryptionResponse {
+        /// Shared secret encrypted with the server's public key
+        pub shared_secret: Vec<u8>,
+        /// Verify token encrypted with the server's public key
+        pub verify_token: Vec<u8>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x02)]
+    pub struct LoginPluginResponse {
+        pub message_id: VarInt,
+        /// `None` if the client does not understand the channel
+        pub data: Option<RestBuffer>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x03)]
+    pub struct LoginAcknowledged;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x04)]
+    pub struct CookieResponse {
+        pub key: String,
+        pub payload: Option<Vec<u8>>,
+    }
+}
+
+pub mod clientbound {
+    use crate::{
+        Deserialize, Packet, Serialize,
+        ser::{PrefixedArray, RestBuffer},
+        varint::VarInt,
+    };
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x00)]
+    pub struct Disconnect {
+        /// JSON text component
+        pub reason: String,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x01)]
+    pub struct EncryptionRequest {
+        /// Empty on vanilla servers
+        pub server_id: String,
+        /// DER-encoded RSA public key
+        pub public_key: Vec<u8>,
+        pub verify_token: Vec<u8>,
+        pub should_authenticate: bool,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x02)]
+    pub struct LoginSuccess {
+        pub uuid: u128,
+        pub username: String,
+        pub properties: PrefixedArray<Property>,
+        pub strict_error_handling: bool,
+    }
+
+    /// Also the session server's JSON form, re-exported as
+    /// [`ProfileProperty`](crate::profile::ProfileProperty).
+    #[derive(
+        Serialize, Deserialize, serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq,
+    )]
+    pub struct Property {
+        pub name: String,
+        pub value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub signature: Option<String>,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x03)]
+    pub struct SetCompression {
+        /// Packets of this size or larger are compressed, negative disables compression
+        pub threshold: VarInt,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x04)]
+    pub struct LoginPluginRequest {
+        pub message_id: VarInt,
+        pub channel: String,
+        pub data: RestBuffer,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x05)]
+    pub struct CookieRequest {
+        pub key: String,
+    }
+}
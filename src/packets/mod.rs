@@ -0,0 +1,15 @@
+//! Ready-made packet definitions for [`PROTOCOL_VERSION`].
+//!
+//! Each state has a `serverbound` (client to server) and a `clientbound`
+//! (server to client) module.
+
+use crate::version::ProtocolVersion;
+
+pub mod configuration;
+pub mod handshake;
+pub mod login;
+pub mod play;
+pub mod status;
+
+/// Protocol version the packets in this module are defined for.
+pub const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_21;
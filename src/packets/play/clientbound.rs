@@ -0,0 +1,1684 @@
+use std::io::{Read, Write};
+
+use crate::{
+    Deserialize, Packet, Serialize,
+    nbt::Nbt,
+    packets::{
+        configuration::clientbound::{RegistryTags, ReportDetail, ServerLink},
+        login::clientbound::Property,
+    },
+    ser::{self, PrefixedArray, RestBuffer, SerializationError},
+    varint::{VarInt, VarLong},
+};
+
+use super::{IdOr, MessageSignature, Position, SoundEvent, Vec3, play_packets};
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x00)]
+pub struct BundleDelimiter;
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x01)]
+pub struct SpawnEntity {
+    pub entity_id: VarInt,
+    pub entity_uuid: u128,
+    pub entity_type: VarInt,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Angle in 1/256 of a full turn
+    pub pitch: u8,
+    pub yaw: u8,
+    pub head_yaw: u8,
+    pub data: VarInt,
+    pub velocity_x: i16,
+    pub velocity_y: i16,
+    pub velocity_z: i16,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x02)]
+pub struct SpawnExperienceOrb {
+    pub entity_id: VarInt,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub count: i16,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x03)]
+pub struct EntityAnimation {
+    pub entity_id: VarInt,
+    pub animation: u8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x04)]
+pub struct AwardStatistics {
+    pub statistics: PrefixedArray<Statistic>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Statistic {
+    pub category_id: VarInt,
+    pub statistic_id: VarInt,
+    pub value: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x05)]
+pub struct AcknowledgeBlockChange {
+    pub sequence_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x06)]
+pub struct SetBlockDestroyStage {
+    pub entity_id: VarInt,
+    pub location: Position,
+    /// 0-9, anything else removes the animation
+    pub destroy_stage: u8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x07)]
+pub struct BlockEntityData {
+    pub location: Position,
+    pub block_entity_type: VarInt,
+    pub nbt_data: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x08)]
+pub struct BlockAction {
+    pub location: Position,
+    pub action_id: u8,
+    pub action_parameter: u8,
+    pub block_type: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x09)]
+pub struct BlockUpdate {
+    pub location: Position,
+    pub block_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0A)]
+pub struct BossBar {
+    pub uuid: u128,
+    pub action: BossBarAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BossBarAction {
+    Add {
+        title: Nbt,
+        /// 0.0 to 1.0
+        health: f32,
+        /// 0: pink, 1: blue, 2: red, 3: green, 4: yellow, 5: purple, 6: white
+        color: VarInt,
+        /// 0: none, 1: 6 notches, 2: 10 notches, 3: 12 notches, 4: 20 notches
+        division: VarInt,
+        /// 0x01: darken sky, 0x02: dragon bar, 0x04: create fog
+        flags: u8,
+    },
+    Remove,
+    UpdateHealth(f32),
+    UpdateTitle(Nbt),
+    UpdateStyle {
+        color: VarInt,
+        division: VarInt,
+    },
+    UpdateFlags(u8),
+}
+
+impl ser::Serialize for BossBarAction {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            BossBarAction::Add {
+                title,
+                health,
+                color,
+                division,
+                flags,
+            } => {
+                VarInt(0).serialize(writer)?;
+                title.serialize(writer)?;
+                health.serialize(writer)?;
+                color.serialize(writer)?;
+                division.serialize(writer)?;
+                flags.serialize(writer)
+            }
+            BossBarAction::Remove => VarInt(1).serialize(writer),
+            BossBarAction::UpdateHealth(health) => {
+                VarInt(2).serialize(writer)?;
+                health.serialize(writer)
+            }
+            BossBarAction::UpdateTitle(title) => {
+                VarInt(3).serialize(writer)?;
+                title.serialize(writer)
+            }
+            BossBarAction::UpdateStyle { color, division } => {
+                VarInt(4).serialize(writer)?;
+                color.serialize(writer)?;
+                division.serialize(writer)
+            }
+            BossBarAction::UpdateFlags(flags) => {
+                VarInt(5).serialize(writer)?;
+                flags.serialize(writer)
+            }
+        }
+    }
+}
+
+impl ser::Deserialize for BossBarAction {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match VarInt::deserialize(reader)?.0 {
+            0 => Ok(BossBarAction::Add {
+                title: Nbt::deserialize(reader)?,
+                health: f32::deserialize(reader)?,
+                color: VarInt::deserialize(reader)?,
+                division: VarInt::deserialize(reader)?,
+                flags: u8::deserialize(reader)?,
+            }),
+            1 => Ok(BossBarAction::Remove),
+            2 => Ok(BossBarAction::UpdateHealth(f32::deserialize(reader)?)),
+            3 => Ok(BossBarAction::UpdateTitle(Nbt::deserialize(reader)?)),
+            4 => Ok(BossBarAction::UpdateStyle {
+                color: VarInt::deserialize(reader)?,
+                division: VarInt::deserialize(reader)?,
+            }),
+            5 => Ok(BossBarAction::UpdateFlags(u8::deserialize(reader)?)),
+            _ => Err(SerializationError::InvalidValue("boss bar action")),
+        }
+    }
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0B)]
+pub struct ChangeDifficulty {
+    pub difficulty: u8,
+    pub difficulty_locked: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0C)]
+pub struct ChunkBatchFinished {
+    pub batch_size: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0D)]
+pub struct ChunkBatchStart;
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0E)]
+pub struct ChunkBiomes {
+    pub chunk_biome_data: PrefixedArray<ChunkBiomeData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChunkBiomeData {
+    pub chunk_z: i32,
+    pub chunk_x: i32,
+    pub data: Vec<u8>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0F)]
+pub struct ClearTitles {
+    pub reset: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x10)]
+pub struct CommandSuggestionsResponse {
+    pub id: VarInt,
+    pub start: VarInt,
+    pub length: VarInt,
+    pub matches: PrefixedArray<CommandSuggestion>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CommandSuggestion {
+    pub text: String,
+    pub tooltip: Option<Nbt>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x11)]
+pub struct Commands {
+    /// Command graph nodes followed by the root index
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x12)]
+pub struct CloseContainer {
+    pub window_id: u8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x13)]
+pub struct SetContainerContent {
+    pub window_id: u8,
+    pub state_id: VarInt,
+    /// Slot data and carried item
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x14)]
+pub struct SetContainerProperty {
+    pub window_id: u8,
+    pub property: i16,
+    pub value: i16,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x15)]
+pub struct SetContainerSlot {
+    pub window_id: i8,
+    pub state_id: VarInt,
+    pub slot: i16,
+    pub slot_data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x16)]
+pub struct CookieRequest {
+    pub key: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x17)]
+pub struct SetCooldown {
+    pub item_id: VarInt,
+    pub cooldown_ticks: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x18)]
+pub struct ChatSuggestions {
+    /// 0: add, 1: remove, 2: set
+    pub action: VarInt,
+    pub entries: PrefixedArray<String>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x19)]
+pub struct PluginMessage {
+    pub channel: String,
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1A)]
+pub struct DamageEvent {
+    pub entity_id: VarInt,
+    pub source_type_id: VarInt,
+    /// Entity ID + 1, 0 if absent
+    pub source_cause_id: VarInt,
+    /// Entity ID + 1, 0 if absent
+    pub source_direct_id: VarInt,
+    pub source_position: Option<Vec3>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1B)]
+pub struct DebugSample {
+    pub sample: PrefixedArray<i64>,
+    pub sample_type: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1C)]
+pub struct DeleteMessage {
+    pub signature: MessageSignature,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1D)]
+pub struct Disconnect {
+    pub reason: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1E)]
+pub struct DisguisedChatMessage {
+    pub message: Nbt,
+    pub chat_type: IdOr<ChatType>,
+    pub sender_name: Nbt,
+    pub target_name: Option<Nbt>,
+}
+
+/// Entry of the `minecraft:chat_type` registry.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatType {
+    pub chat: ChatTypeDecoration,
+    pub narration: ChatTypeDecoration,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatTypeDecoration {
+    pub translation_key: String,
+    /// 0: sender, 1: target, 2: content
+    pub parameters: PrefixedArray<VarInt>,
+    pub style: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1F)]
+pub struct EntityEvent {
+    pub entity_id: i32,
+    pub entity_status: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x20)]
+pub struct Explosion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub strength: f32,
+    /// Affected blocks, player motion, block interaction, particles and sound
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x21)]
+pub struct UnloadChunk {
+    pub chunk_z: i32,
+    pub chunk_x: i32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x22)]
+pub struct GameEvent {
+    pub event: u8,
+    pub value: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x23)]
+pub struct OpenHorseScreen {
+    pub window_id: u8,
+    pub slot_count: VarInt,
+    pub entity_id: i32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x24)]
+pub struct HurtAnimation {
+    pub entity_id: VarInt,
+    pub yaw: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x25)]
+pub struct InitializeWorldBorder {
+    pub x: f64,
+    pub z: f64,
+    pub old_diameter: f64,
+    pub new_diameter: f64,
+    pub speed: VarLong,
+    pub portal_teleport_boundary: VarInt,
+    pub warning_blocks: VarInt,
+    pub warning_time: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x26)]
+pub struct KeepAlive {
+    pub keep_alive_id: i64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x27)]
+pub struct ChunkDataAndUpdateLight {
+    pub chunk_x: i32,
+    pub chunk_z: i32,
+    pub heightmaps: Nbt,
+    /// Chunk sections
+    pub data: Vec<u8>,
+    pub block_entities: PrefixedArray<ChunkBlockEntity>,
+    pub light: LightData,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChunkBlockEntity {
+    /// `((x & 15) << 4) | (z & 15)` relative to the chunk
+    pub packed_xz: u8,
+    pub y: i16,
+    pub block_entity_type: VarInt,
+    pub data: Nbt,
+}
+
+/// Light data shared by Chunk Data and Update Light.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct LightData {
+    pub sky_light_mask: PrefixedArray<i64>,
+    pub block_light_mask: PrefixedArray<i64>,
+    pub empty_sky_light_mask: PrefixedArray<i64>,
+    pub empty_block_light_mask: PrefixedArray<i64>,
+    /// 2048 bytes per section in the mask
+    pub sky_light_arrays: PrefixedArray<Vec<u8>>,
+    pub block_light_arrays: PrefixedArray<Vec<u8>>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x28)]
+pub struct WorldEvent {
+    pub event: i32,
+    pub location: Position,
+    pub data: i32,
+    pub disable_relative_volume: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x29)]
+pub struct Particle {
+    pub long_distance: bool,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub offset_z: f32,
+    pub max_speed: f32,
+    pub particle_count: i32,
+    pub particle_id: VarInt,
+    /// Particle-specific options
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2A)]
+pub struct UpdateLight {
+    pub chunk_x: VarInt,
+    pub chunk_z: VarInt,
+    pub light: LightData,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2B)]
+pub struct Login {
+    pub entity_id: i32,
+    pub is_hardcore: bool,
+    pub dimension_names: PrefixedArray<String>,
+    pub max_players: VarInt,
+    pub view_distance: VarInt,
+    pub simulation_distance: VarInt,
+    pub reduced_debug_info: bool,
+    pub enable_respawn_screen: bool,
+    pub do_limited_crafting: bool,
+    pub spawn_info: SpawnInfo,
+    pub enforces_secure_chat: bool,
+}
+
+/// World and game mode fields shared by Login and Respawn.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SpawnInfo {
+    pub dimension_type: VarInt,
+    pub dimension_name: String,
+    /// First 8 bytes of the SHA-256 of the world seed
+    pub hashed_seed: i64,
+    pub game_mode: u8,
+    /// -1 if undefined
+    pub previous_game_mode: i8,
+    pub is_debug: bool,
+    pub is_flat: bool,
+    pub death_location: Option<DeathLocation>,
+    pub portal_cooldown: VarInt,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeathLocation {
+    pub dimension_name: String,
+    pub location: Position,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2C)]
+pub struct MapData {
+    pub map_id: VarInt,
+    pub scale: i8,
+    pub locked: bool,
+    /// Icons and color patch
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2D)]
+pub struct MerchantOffers {
+    pub window_id: VarInt,
+    /// Trades, villager level, experience and flags
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2E)]
+pub struct UpdateEntityPosition {
+    pub entity_id: VarInt,
+    /// `(current * 4096) - (previous * 4096)`
+    pub delta_x: i16,
+    pub delta_y: i16,
+    pub delta_z: i16,
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2F)]
+pub struct UpdateEntityPositionAndRotation {
+    pub entity_id: VarInt,
+    pub delta_x: i16,
+    pub delta_y: i16,
+    pub delta_z: i16,
+    pub yaw: u8,
+    pub pitch: u8,
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x30)]
+pub struct UpdateEntityRotation {
+    pub entity_id: VarInt,
+    pub yaw: u8,
+    pub pitch: u8,
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x31)]
+pub struct MoveVehicle {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x32)]
+pub struct OpenBook {
+    pub hand: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x33)]
+pub struct OpenScreen {
+    pub window_id: VarInt,
+    pub window_type: VarInt,
+    pub window_title: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x34)]
+pub struct OpenSignEditor {
+    pub location: Position,
+    pub is_front_text: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x35)]
+pub struct Ping {
+    pub id: i32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x36)]
+pub struct PingResponse {
+    pub payload: i64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x37)]
+pub struct PlaceGhostRecipe {
+    pub window_id: i8,
+    pub recipe: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x38)]
+pub struct PlayerAbilities {
+    pub flags: i8,
+    pub flying_speed: f32,
+    pub field_of_view_modifier: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x39)]
+pub struct PlayerChatMessage {
+    pub sender: u128,
+    pub index: VarInt,
+    pub message_signature: Option<Box<[u8; 256]>>,
+    pub message: String,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub previous_messages: PrefixedArray<MessageSignature>,
+    pub unsigned_content: Option<Nbt>,
+    pub filter: FilterMask,
+    pub chat_type: IdOr<ChatType>,
+    pub sender_name: Nbt,
+    pub target_name: Option<Nbt>,
+}
+
+/// Which characters of a chat message the server filtered out.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterMask {
+    PassThrough,
+    FullyFiltered,
+    /// Bit set of filtered characters, 64 per long
+    PartiallyFiltered(PrefixedArray<i64>),
+}
+
+impl ser::Serialize for FilterMask {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            FilterMask::PassThrough => VarInt(0).serialize(writer),
+            FilterMask::FullyFiltered => VarInt(1).serialize(writer),
+            FilterMask::PartiallyFiltered(mask) => {
+                VarInt(2).serialize(writer)?;
+                mask.serialize(writer)
+            }
+        }
+    }
+}
+
+impl ser::Deserialize for FilterMask {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match VarInt::deserialize(reader)?.0 {
+            0 => Ok(FilterMask::PassThrough),
+            1 => Ok(FilterMask::FullyFiltered),
+            2 => Ok(FilterMask::PartiallyFiltered(PrefixedArray::deserialize(
+                reader,
+            )?)),
+            _ => Err(SerializationError::InvalidValue("filter type")),
+        }
+    }
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x3A)]
+pub struct EndCombat {
+    pub duration: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x3B)]
+pub struct EnterCombat;
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x3C)]
+pub struct CombatDeath {
+    pub player_id: VarInt,
+    pub message: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x3D)]
+pub struct PlayerInfoRemove {
+    pub uuids: PrefixedArray<u128>,
+}
+
+/// Player list changes. Which fields of [`PlayerInfoEntry`] are present on the
+/// wire is decided by [`PlayerInfoUpdate::actions`].
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x3E)]
+pub struct PlayerInfoUpdate {
+    pub update: PlayerInfoActions,
+}
+
+impl PlayerInfoUpdate {
+    pub const ADD_PLAYER: u8 = 0x01;
+    pub const INITIALIZE_CHAT: u8 = 0x02;
+    pub const UPDATE_GAME_MODE: u8 = 0x04;
+    pub const UPDATE_LISTED: u8 = 0x08;
+    pub const UPDATE_LATENCY: u8 = 0x10;
+    pub const UPDATE_DISPLAY_NAME: u8 = 0x20;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerInfoActions {
+    /// Bit set of `PlayerInfoUpdate::ADD_PLAYER` etc.
+    pub actions: u8,
+    pub players: Vec<PlayerInfoEntry>,
+}
+
+/// Fields are `Some` exactly when their action bit is set.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlayerInfoEntry {
+    pub uuid: u128,
+    pub add_player: Option<AddPlayer>,
+    /// Outer `Option` is the action bit, inner one is the wire value
+    pub initialize_chat: Option<Option<ChatSession>>,
+    pub game_mode: Option<VarInt>,
+    pub listed: Option<bool>,
+    pub latency: Option<VarInt>,
+    /// Outer `Option` is the action bit, inner one is the wire value
+    pub display_name: Option<Option<Nbt>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AddPlayer {
+    pub name: String,
+    pub properties: PrefixedArray<Property>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatSession {
+    pub chat_session_id: u128,
+    pub public_key_expiry_time: i64,
+    pub encoded_public_key: Vec<u8>,
+    pub public_key_signature: Vec<u8>,
+}
+
+/// Writes `value` if the action bit is set. A missing value is an error since
+/// the packet would otherwise be malformed.
+fn write_action<T: ser::Serialize, W: Write + Unpin>(
+    actions: u8,
+    bit: u8,
+    field: &'static str,
+    value: &Option<T>,
+    writer: &mut W,
+) -> Result<(), SerializationError> {
+    if actions & bit == 0 {
+        return Ok(());
+    }
+
+    match value {
+        Some(value) => value.serialize(writer),
+        None => Err(SerializationError::MissingField(field)),
+    }
+}
+
+fn read_action<T: ser::Deserialize, R: Read + Unpin>(
+    actions: u8,
+    bit: u8,
+    reader: &mut R,
+) -> Result<Option<T>, SerializationError> {
+    if actions & bit == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(T::deserialize(reader)?))
+}
+
+impl ser::Serialize for PlayerInfoActions {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        let actions = self.actions;
+        actions.serialize(writer)?;
+        VarInt(self.players.len() as i32).serialize(writer)?;
+
+        for player in &self.players {
+            player.uuid.serialize(writer)?;
+            write_action(
+                actions,
+                PlayerInfoUpdate::ADD_PLAYER,
+                "add_player",
+                &player.add_player,
+                writer,
+            )?;
+            write_action(
+                actions,
+                PlayerInfoUpdate::INITIALIZE_CHAT,
+                "initialize_chat",
+                &player.initialize_chat,
+                writer,
+            )?;
+            write_action(
+                actions,
+                PlayerInfoUpdate::UPDATE_GAME_MODE,
+                "game_mode",
+                &player.game_mode,
+                writer,
+            )?;
+            write_action(
+                actions,
+                PlayerInfoUpdate::UPDATE_LISTED,
+                "listed",
+                &player.listed,
+                writer,
+            )?;
+            write_action(
+                actions,
+                PlayerInfoUpdate::UPDATE_LATENCY,
+                "latency",
+                &player.latency,
+                writer,
+            )?;
+            write_action(
+                actions,
+                PlayerInfoUpdate::UPDATE_DISPLAY_NAME,
+                "display_name",
+                &player.display_name,
+                writer,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ser::Deserialize for PlayerInfoActions {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        let actions = u8::deserialize(reader)?;
+        let count = VarInt::deserialize(reader)?.0;
+
+        let mut players = Vec::new();
+        for _ in 0..count {
+            players.push(PlayerInfoEntry {
+                uuid: u128::deserialize(reader)?,
+                add_player: read_action(actions, PlayerInfoUpdate::ADD_PLAYER, reader)?,
+                initialize_chat: read_action(actions, PlayerInfoUpdate::INITIALIZE_CHAT, reader)?,
+                game_mode: read_action(actions, PlayerInfoUpdate::UPDATE_GAME_MODE, reader)?,
+                listed: read_action(actions, PlayerInfoUpdate::UPDATE_LISTED, reader)?,
+                latency: read_action(actions, PlayerInfoUpdate::UPDATE_LATENCY, reader)?,
+                display_name: read_action(actions, PlayerInfoUpdate::UPDATE_DISPLAY_NAME, reader)?,
+            });
+        }
+
+        Ok(Self { actions, players })
+    }
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x3F)]
+pub struct LookAt {
+    /// 0: feet, 1: eyes
+    pub feet_eyes: VarInt,
+    pub target_x: f64,
+    pub target_y: f64,
+    pub target_z: f64,
+    pub entity: Option<LookAtEntity>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LookAtEntity {
+    pub entity_id: VarInt,
+    pub entity_feet_eyes: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x40)]
+pub struct SynchronizePlayerPosition {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Bit set of coordinates that are relative
+    pub flags: i8,
+    pub teleport_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x41)]
+pub struct UpdateRecipeBook {
+    /// 0: init, 1: add, 2: remove
+    pub action: VarInt,
+    /// Book settings and recipe IDs
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x42)]
+pub struct RemoveEntities {
+    pub entity_ids: PrefixedArray<VarInt>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x43)]
+pub struct RemoveEntityEffect {
+    pub entity_id: VarInt,
+    pub effect_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x44)]
+pub struct ResetScore {
+    pub entity_name: String,
+    pub objective_name: Option<String>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x45)]
+pub struct RemoveResourcePack {
+    pub uuid: Option<u128>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x46)]
+pub struct AddResourcePack {
+    pub uuid: u128,
+    pub url: String,
+    pub hash: String,
+    pub forced: bool,
+    pub prompt_message: Option<Nbt>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x47)]
+pub struct Respawn {
+    pub spawn_info: SpawnInfo,
+    /// 0x01: keep attributes, 0x02: keep metadata
+    pub data_kept: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x48)]
+pub struct SetHeadRotation {
+    pub entity_id: VarInt,
+    pub head_yaw: u8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x49)]
+pub struct UpdateSectionBlocks {
+    pub chunk_section_position: i64,
+    /// `block_state_id << 12 | (x << 8 | z << 4 | y)`
+    pub blocks: PrefixedArray<VarLong>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x4A)]
+pub struct SelectAdvancementsTab {
+    pub identifier: Option<String>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x4B)]
+pub struct ServerData {
+    pub motd: Nbt,
+    /// PNG bytes
+    pub icon: Option<Vec<u8>>,
+    pub enforces_secure_chat: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x4C)]
+pub struct SetActionBarText {
+    pub action_bar_text: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x4D)]
+pub struct SetBorderCenter {
+    pub x: f64,
+    pub z: f64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x4E)]
+pub struct SetBorderLerpSize {
+    pub old_diameter: f64,
+    pub new_diameter: f64,
+    pub speed: VarLong,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x4F)]
+pub struct SetBorderSize {
+    pub diameter: f64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x50)]
+pub struct SetBorderWarningDelay {
+    pub warning_time: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x51)]
+pub struct SetBorderWarningDistance {
+    pub warning_blocks: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x52)]
+pub struct SetCamera {
+    pub camera_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x53)]
+pub struct SetHeldItem {
+    pub slot: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x54)]
+pub struct SetCenterChunk {
+    pub chunk_x: VarInt,
+    pub chunk_z: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x55)]
+pub struct SetRenderDistance {
+    pub view_distance: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x56)]
+pub struct SetDefaultSpawnPosition {
+    pub location: Position,
+    pub angle: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x57)]
+pub struct DisplayObjective {
+    pub position: VarInt,
+    pub score_name: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x58)]
+pub struct SetEntityMetadata {
+    pub entity_id: VarInt,
+    pub metadata: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x59)]
+pub struct LinkEntities {
+    pub attached_entity_id: i32,
+    pub holding_entity_id: i32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x5A)]
+pub struct SetEntityVelocity {
+    pub entity_id: VarInt,
+    pub velocity_x: i16,
+    pub velocity_y: i16,
+    pub velocity_z: i16,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x5B)]
+pub struct SetEquipment {
+    pub entity_id: VarInt,
+    pub equipment: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x5C)]
+pub struct SetExperience {
+    pub experience_bar: f32,
+    pub level: VarInt,
+    pub total_experience: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x5D)]
+pub struct SetHealth {
+    pub health: f32,
+    pub food: VarInt,
+    pub food_saturation: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x5E)]
+pub struct UpdateObjectives {
+    pub objective_name: String,
+    pub action: ObjectiveAction,
+}
+
+/// Encoded as a byte mode: 0 create, 1 remove, 2 update.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectiveAction {
+    Create(Objective),
+    Remove,
+    Update(Objective),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Objective {
+    pub display_name: Nbt,
+    /// 0: integer, 1: hearts
+    pub render_type: VarInt,
+    pub number_format: Option<NumberFormat>,
+}
+
+/// How scores are displayed in the sidebar and player list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberFormat {
+    Blank,
+    /// Style compound applied to the number
+    Styled(Nbt),
+    /// Text shown instead of the number
+    Fixed(Nbt),
+}
+
+impl ser::Serialize for ObjectiveAction {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            ObjectiveAction::Create(objective) => {
+                0i8.serialize(writer)?;
+                objective.serialize(writer)
+            }
+            ObjectiveAction::Remove => 1i8.serialize(writer),
+            ObjectiveAction::Update(objective) => {
+                2i8.serialize(writer)?;
+                objective.serialize(writer)
+            }
+        }
+    }
+}
+
+impl ser::Deserialize for ObjectiveAction {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match i8::deserialize(reader)? {
+            0 => Ok(ObjectiveAction::Create(Objective::deserialize(reader)?)),
+            1 => Ok(ObjectiveAction::Remove),
+            2 => Ok(ObjectiveAction::Update(Objective::deserialize(reader)?)),
+            _ => Err(SerializationError::InvalidValue("objective mode")),
+        }
+    }
+}
+
+impl ser::Serialize for NumberFormat {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            NumberFormat::Blank => VarInt(0).serialize(writer),
+            NumberFormat::Styled(style) => {
+                VarInt(1).serialize(writer)?;
+                style.serialize(writer)
+            }
+            NumberFormat::Fixed(content) => {
+                VarInt(2).serialize(writer)?;
+                content.serialize(writer)
+            }
+        }
+    }
+}
+
+impl ser::Deserialize for NumberFormat {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match VarInt::deserialize(reader)?.0 {
+            0 => Ok(NumberFormat::Blank),
+            1 => Ok(NumberFormat::Styled(Nbt::deserialize(reader)?)),
+            2 => Ok(NumberFormat::Fixed(Nbt::deserialize(reader)?)),
+            _ => Err(SerializationError::InvalidValue("number format")),
+        }
+    }
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x5F)]
+pub struct SetPassengers {
+    pub entity_id: VarInt,
+    pub passengers: PrefixedArray<VarInt>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x60)]
+pub struct UpdateTeams {
+    pub team_name: String,
+    pub action: TeamAction,
+}
+
+/// Encoded as a byte mode from 0 to 4, in variant order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeamAction {
+    Create {
+        info: TeamInfo,
+        /// Player names and entity UUIDs
+        entities: PrefixedArray<String>,
+    },
+    Remove,
+    Update(TeamInfo),
+    AddEntities(PrefixedArray<String>),
+    RemoveEntities(PrefixedArray<String>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TeamInfo {
+    pub display_name: Nbt,
+    /// 0x01: friendly fire, 0x02: see invisible teammates
+    pub friendly_flags: i8,
+    /// `always`, `hideForOtherTeams`, `hideForOwnTeam` or `never`
+    pub name_tag_visibility: String,
+    /// `always`, `pushOtherTeams`, `pushOwnTeam` or `never`
+    pub collision_rule: String,
+    /// Chat formatting color, 0-15 or 21 for reset
+    pub color: VarInt,
+    pub prefix: Nbt,
+    pub suffix: Nbt,
+}
+
+impl ser::Serialize for TeamAction {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            TeamAction::Create { info, entities } => {
+                0i8.serialize(writer)?;
+                info.serialize(writer)?;
+                entities.serialize(writer)
+            }
+            TeamAction::Remove => 1i8.serialize(writer),
+            TeamAction::Update(info) => {
+                2i8.serialize(writer)?;
+                info.serialize(writer)
+            }
+            TeamAction::AddEntities(entities) => {
+                3i8.serialize(writer)?;
+                entities.serialize(writer)
+            }
+            TeamAction::RemoveEntities(entities) => {
+                4i8.serialize(writer)?;
+                entities.serialize(writer)
+            }
+        }
+    }
+}
+
+impl ser::Deserialize for TeamAction {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match i8::deserialize(reader)? {
+            0 => Ok(TeamAction::Create {
+                info: TeamInfo::deserialize(reader)?,
+                entities: PrefixedArray::deserialize(reader)?,
+            }),
+            1 => Ok(TeamAction::Remove),
+            2 => Ok(TeamAction::Update(TeamInfo::deserialize(reader)?)),
+            3 => Ok(TeamAction::AddEntities(PrefixedArray::deserialize(reader)?)),
+            4 => Ok(TeamAction::RemoveEntities(PrefixedArray::deserialize(
+                reader,
+            )?)),
+            _ => Err(SerializationError::InvalidValue("team mode")),
+        }
+    }
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x61)]
+pub struct UpdateScore {
+    pub entity_name: String,
+    pub objective_name: String,
+    pub value: VarInt,
+    pub display_name: Option<Nbt>,
+    pub number_format: Option<NumberFormat>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x62)]
+pub struct SetSimulationDistance {
+    pub simulation_distance: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x63)]
+pub struct SetSubtitleText {
+    pub subtitle_text: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x64)]
+pub struct UpdateTime {
+    pub world_age: i64,
+    pub time_of_day: i64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x65)]
+pub struct SetTitleText {
+    pub title_text: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x66)]
+pub struct SetTitleAnimationTimes {
+    pub fade_in: i32,
+    pub stay: i32,
+    pub fade_out: i32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x67)]
+pub struct EntitySoundEffect {
+    pub sound: IdOr<SoundEvent>,
+    pub sound_category: VarInt,
+    pub entity_id: VarInt,
+    pub volume: f32,
+    pub pitch: f32,
+    pub seed: i64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x68)]
+pub struct SoundEffect {
+    pub sound: IdOr<SoundEvent>,
+    pub sound_category: VarInt,
+    /// Fixed-point, multiplied by 8
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub volume: f32,
+    pub pitch: f32,
+    pub seed: i64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x69)]
+pub struct StartConfiguration;
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x6A)]
+pub struct StopSound {
+    pub filter: StopSoundFilter,
+}
+
+/// Encoded as a flags byte, 0x01 if `source` is present and 0x02 if `sound` is.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StopSoundFilter {
+    /// Sound category, `None` for every category
+    pub source: Option<VarInt>,
+    /// Sound identifier, `None` for every sound
+    pub sound: Option<String>,
+}
+
+impl ser::Serialize for StopSoundFilter {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        let flags = self.source.is_some() as i8 | (self.sound.is_some() as i8) << 1;
+        flags.serialize(writer)?;
+        if let Some(source) = &self.source {
+            source.serialize(writer)?;
+        }
+        if let Some(sound) = &self.sound {
+            sound.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl ser::Deserialize for StopSoundFilter {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        let flags = i8::deserialize(reader)?;
+        let source = match flags & 0x01 {
+            0 => None,
+            _ => Some(VarInt::deserialize(reader)?),
+        };
+        let sound = match flags & 0x02 {
+            0 => None,
+            _ => Some(String::deserialize(reader)?),
+        };
+
+        Ok(Self { source, sound })
+    }
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x6B)]
+pub struct StoreCookie {
+    pub key: String,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x6C)]
+pub struct SystemChatMessage {
+    pub content: Nbt,
+    /// Shown above the hotbar instead of in chat
+    pub overlay: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x6D)]
+pub struct SetTabListHeaderAndFooter {
+    pub header: Nbt,
+    pub footer: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x6E)]
+pub struct TagQueryResponse {
+    pub transaction_id: VarInt,
+    pub nbt: Nbt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x6F)]
+pub struct PickupItem {
+    pub collected_entity_id: VarInt,
+    pub collector_entity_id: VarInt,
+    pub pickup_item_count: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x70)]
+pub struct TeleportEntity {
+    pub entity_id: VarInt,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: u8,
+    pub pitch: u8,
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x71)]
+pub struct SetTickingState {
+    pub tick_rate: f32,
+    pub is_frozen: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x72)]
+pub struct StepTick {
+    pub tick_steps: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x73)]
+pub struct Transfer {
+    pub host: String,
+    pub port: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x74)]
+pub struct UpdateAdvancements {
+    pub reset_clear: bool,
+    /// Advancement mapping, removed identifiers and progress
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x75)]
+pub struct UpdateAttributes {
+    pub entity_id: VarInt,
+    pub properties: PrefixedArray<AttributeProperty>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttributeProperty {
+    pub id: VarInt,
+    pub value: f64,
+    pub modifiers: PrefixedArray<AttributeModifier>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttributeModifier {
+    pub id: String,
+    pub amount: f64,
+    /// 0: add, 1: multiply base, 2: multiply total
+    pub operation: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x76)]
+pub struct EntityEffect {
+    pub entity_id: VarInt,
+    pub effect_id: VarInt,
+    pub amplifier: VarInt,
+    /// -1 for infinite
+    pub duration: VarInt,
+    pub flags: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x77)]
+pub struct UpdateRecipes {
+    pub recipes: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x78)]
+pub struct UpdateTags {
+    pub registries: PrefixedArray<RegistryTags>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x79)]
+pub struct ProjectilePower {
+    pub entity_id: VarInt,
+    pub power: f64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x7A)]
+pub struct CustomReportDetails {
+    pub details: PrefixedArray<ReportDetail>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x7B)]
+pub struct ServerLinks {
+    pub links: PrefixedArray<ServerLink>,
+}
+
+play_packets! {
+    /// Any clientbound Play packet.
+    ClientboundPlayPacket {
+        BundleDelimiter,
+        SpawnEntity,
+        SpawnExperienceOrb,
+        EntityAnimation,
+        AwardStatistics,
+        AcknowledgeBlockChange,
+        SetBlockDestroyStage,
+        BlockEntityData,
+        BlockAction,
+        BlockUpdate,
+        BossBar,
+        ChangeDifficulty,
+        ChunkBatchFinished,
+        ChunkBatchStart,
+        ChunkBiomes,
+        ClearTitles,
+        CommandSuggestionsResponse,
+        Commands,
+        CloseContainer,
+        SetContainerContent,
+        SetContainerProperty,
+        SetContainerSlot,
+        CookieRequest,
+        SetCooldown,
+        ChatSuggestions,
+        PluginMessage,
+        DamageEvent,
+        DebugSample,
+        DeleteMessage,
+        Disconnect,
+        DisguisedChatMessage,
+        EntityEvent,
+        Explosion,
+        UnloadChunk,
+        GameEvent,
+        OpenHorseScreen,
+        HurtAnimation,
+        InitializeWorldBorder,
+        KeepAlive,
+        ChunkDataAndUpdateLight,
+        WorldEvent,
+        Particle,
+        UpdateLight,
+        Login,
+        MapData,
+        MerchantOffers,
+        UpdateEntityPosition,
+        UpdateEntityPositionAndRotation,
+        UpdateEntityRotation,
+        MoveVehicle,
+        OpenBook,
+        OpenScreen,
+        OpenSignEditor,
+        Ping,
+        PingResponse,
+        PlaceGhostRecipe,
+        PlayerAbilities,
+        PlayerChatMessage,
+        EndCombat,
+        EnterCombat,
+        CombatDeath,
+        PlayerInfoRemove,
+        PlayerInfoUpdate,
+        LookAt,
+        SynchronizePlayerPosition,
+        UpdateRecipeBook,
+        RemoveEntities,
+        RemoveEntityEffect,
+        ResetScore,
+        RemoveResourcePack,
+        AddResourcePack,
+        Respawn,
+        SetHeadRotation,
+        UpdateSectionBlocks,
+        SelectAdvancementsTab,
+        ServerData,
+        SetActionBarText,
+        SetBorderCenter,
+        SetBorderLerpSize,
+        SetBorderSize,
+        SetBorderWarningDelay,
+        SetBorderWarningDistance,
+        SetCamera,
+        SetHeldItem,
+        SetCenterChunk,
+        SetRenderDistance,
+        SetDefaultSpawnPosition,
+        DisplayObjective,
+        SetEntityMetadata,
+        LinkEntities,
+        SetEntityVelocity,
+        SetEquipment,
+        SetExperience,
+        SetHealth,
+        UpdateObjectives,
+        SetPassengers,
+        UpdateTeams,
+        UpdateScore,
+        SetSimulationDistance,
+        SetSubtitleText,
+        UpdateTime,
+        SetTitleText,
+        SetTitleAnimationTimes,
+        EntitySoundEffect,
+        SoundEffect,
+        StartConfiguration,
+        StopSound,
+        StoreCookie,
+        SystemChatMessage,
+        SetTabListHeaderAndFooter,
+        TagQueryResponse,
+        PickupItem,
+        TeleportEntity,
+        SetTickingState,
+        StepTick,
+        Transfer,
+        UpdateAdvancements,
+        UpdateAttributes,
+        EntityEffect,
+        UpdateRecipes,
+        UpdateTags,
+        ProjectilePower,
+        CustomReportDetails,
+        ServerLinks,
+    }
+}
@@ -0,0 +1,219 @@
+//! Play state packets.
+//!
+//! Every packet ID of [`super::PROTOCOL_VERSION`] has a struct. Fields that
+//! depend on item stacks, entity metadata or other data the crate can't decode
+//! yet are kept as a trailing [`RestBuffer`](crate::ser::RestBuffer), so such
+//! packets still round-trip unchanged.
+//!
+//! [`ClientboundPlayPacket`] and [`ServerboundPlayPacket`] dispatch on the
+//! packet ID and fall back to an `Unknown` variant for IDs they don't know.
+
+use std::io::{Read, Write};
+
+use crate::{
+    Deserialize, Serialize,
+    ser::{self, SerializationError},
+    varint::VarInt,
+};
+
+pub mod clientbound;
+pub mod serverbound;
+
+pub use clientbound::ClientboundPlayPacket;
+pub use serverbound::ServerboundPlayPacket;
+
+/// Block position packed into a 64-bit integer: 26 bits X, 26 bits Z, 12 bits Y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn to_packed(&self) -> i64 {
+        ((self.x as i64 & 0x3FF_FFFF) << 38)
+            | ((self.z as i64 & 0x3FF_FFFF) << 12)
+            | (self.y as i64 & 0xFFF)
+    }
+
+    pub fn from_packed(value: i64) -> Self {
+        Self {
+            x: (value >> 38) as i32,
+            y: (value << 52 >> 52) as i32,
+            z: (value << 26 >> 38) as i32,
+        }
+    }
+}
+
+impl ser::Serialize for Position {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        self.to_packed().serialize(writer)
+    }
+}
+
+impl ser::Deserialize for Position {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        Ok(Self::from_packed(i64::deserialize(reader)?))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Registry reference that is either a numeric ID or an inline definition.
+///
+/// Encoded as a VarInt of `id + 1`, with `0` followed by the inline value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdOr<T> {
+    Id(i32),
+    Inline(T),
+}
+
+impl<T: ser::Serialize> ser::Serialize for IdOr<T> {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            IdOr::Id(id) => VarInt(id + 1).serialize(writer),
+            IdOr::Inline(value) => {
+                VarInt(0).serialize(writer)?;
+                value.serialize(writer)
+            }
+        }
+    }
+}
+
+impl<T: ser::Deserialize> ser::Deserialize for IdOr<T> {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match VarInt::deserialize(reader)?.0 {
+            0 => Ok(IdOr::Inline(T::deserialize(reader)?)),
+            id => Ok(IdOr::Id(id - 1)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SoundEvent {
+    pub sound_name: String,
+    pub fixed_range: Option<f32>,
+}
+
+/// Chat message signature, either by index into the client's cache or in full.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageSignature {
+    Id(i32),
+    Full(Box<[u8; 256]>),
+}
+
+impl ser::Serialize for MessageSignature {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            MessageSignature::Id(id) => VarInt(id + 1).serialize(writer),
+            MessageSignature::Full(signature) => {
+                VarInt(0).serialize(writer)?;
+                signature.serialize(writer)
+            }
+        }
+    }
+}
+
+impl ser::Deserialize for MessageSignature {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match VarInt::deserialize(reader)?.0 {
+            0 => Ok(MessageSignature::Full(Box::new(<[u8; 256]>::deserialize(
+                reader,
+            )?))),
+            id => Ok(MessageSignature::Id(id - 1)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ClientInformation {
+    /// e.g. `en_us`
+    pub locale: String,
+    pub view_distance: i8,
+    /// 0: enabled, 1: commands only, 2: hidden
+    pub chat_mode: VarInt,
+    pub chat_colors: bool,
+    /// Bit mask of visible skin layers
+    pub displayed_skin_parts: u8,
+    /// 0: left, 1: right
+    pub main_hand: VarInt,
+    pub enable_text_filtering: bool,
+    pub allow_server_listings: bool,
+}
+
+/// Generates a packet enum that dispatches on `PACKET_ID`.
+macro_rules! play_packets {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum $name {
+            $($variant($variant),)*
+            /// Packet ID not defined for this protocol version.
+            Unknown(crate::packet::UncompressedPacket),
+        }
+
+        impl $name {
+            /// `(packet ID, name)` of every known packet, in ID order.
+            pub const PACKETS: &[(i32, &'static str)] = &[
+                $(($variant::PACKET_ID.0, stringify!($variant)),)*
+            ];
+
+            /// Decodes `packet` by ID. Unknown IDs are returned as
+            /// [`Self::Unknown`], known IDs must be decoded without leftover bytes.
+            pub fn decode(
+                packet: crate::packet::UncompressedPacket,
+            ) -> Result<Self, crate::ser::SerializationError> {
+                $(
+                    if packet.packet_id == $variant::PACKET_ID {
+                        return Ok(Self::$variant(packet.convert_exact()?));
+                    }
+                )*
+                Ok(Self::Unknown(packet))
+            }
+
+            pub fn as_uncompressed(
+                &self,
+            ) -> Result<crate::packet::UncompressedPacket, crate::ser::SerializationError> {
+                match self {
+                    $(Self::$variant(packet) => packet.as_uncompressed(),)*
+                    Self::Unknown(packet) => Ok(packet.clone()),
+                }
+            }
+
+            pub fn packet_id(&self) -> crate::varint::VarInt {
+                match self {
+                    $(Self::$variant(_) => $variant::PACKET_ID,)*
+                    Self::Unknown(packet) => packet.packet_id.clone(),
+                }
+            }
+
+            /// Name of the packet type, `"Unknown"` for unknown IDs.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => stringify!($variant),)*
+                    Self::Unknown(_) => "Unknown",
+                }
+            }
+        }
+
+        $(
+            impl From<$variant> for $name {
+                fn from(packet: $variant) -> Self {
+                    Self::$variant(packet)
+                }
+            }
+        )*
+    };
+}
+
+use play_packets;
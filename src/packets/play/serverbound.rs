@@ -0,0 +1,598 @@
+use std::io::{Read, Write};
+
+use crate::{
+    Deserialize, Packet, Serialize,
+    ser::{self, PrefixedArray, RestBuffer, SerializationError},
+    varint::{VarInt, VarLong},
+};
+
+use super::{ClientInformation as ClientInformationData, Position, play_packets};
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x00)]
+pub struct ConfirmTeleportation {
+    pub teleport_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x01)]
+pub struct QueryBlockEntityTag {
+    pub transaction_id: VarInt,
+    pub location: Position,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x02)]
+pub struct ChangeDifficulty {
+    pub new_difficulty: u8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x03)]
+pub struct AcknowledgeMessage {
+    pub message_count: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x04)]
+pub struct ChatCommand {
+    /// Without the leading `/`
+    pub command: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x05)]
+pub struct SignedChatCommand {
+    pub command: String,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub argument_signatures: PrefixedArray<ArgumentSignature>,
+    pub message_count: VarInt,
+    /// 20-bit set of acknowledged messages
+    pub acknowledged: [u8; 3],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ArgumentSignature {
+    pub argument_name: String,
+    pub signature: [u8; 256],
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x06)]
+pub struct ChatMessage {
+    pub message: String,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub signature: Option<[u8; 256]>,
+    pub message_count: VarInt,
+    pub acknowledged: [u8; 3],
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x07)]
+pub struct PlayerSession {
+    pub session_id: u128,
+    pub expires_at: i64,
+    pub public_key: Vec<u8>,
+    pub key_signature: Vec<u8>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x08)]
+pub struct ChunkBatchReceived {
+    pub chunks_per_tick: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x09)]
+pub struct ClientStatus {
+    /// 0: perform respawn, 1: request stats
+    pub action_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0A)]
+pub struct ClientInformation {
+    pub information: ClientInformationData,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0B)]
+pub struct CommandSuggestionsRequest {
+    pub transaction_id: VarInt,
+    pub text: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0C)]
+pub struct AcknowledgeConfiguration;
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0D)]
+pub struct ClickContainerButton {
+    pub window_id: i8,
+    pub button_id: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0E)]
+pub struct ClickContainer {
+    pub window_id: u8,
+    pub state_id: VarInt,
+    pub slot: i16,
+    pub button: i8,
+    pub mode: VarInt,
+    /// Changed slots and carried item
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x0F)]
+pub struct CloseContainer {
+    pub window_id: u8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x10)]
+pub struct ChangeContainerSlotState {
+    pub slot_id: VarInt,
+    pub window_id: VarInt,
+    pub state: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x11)]
+pub struct CookieResponse {
+    pub key: String,
+    pub payload: Option<Vec<u8>>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x12)]
+pub struct PluginMessage {
+    pub channel: String,
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x13)]
+pub struct DebugSampleSubscription {
+    pub sample_type: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x14)]
+pub struct EditBook {
+    pub slot: VarInt,
+    pub entries: PrefixedArray<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x15)]
+pub struct QueryEntityTag {
+    pub transaction_id: VarInt,
+    pub entity_id: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x16)]
+pub struct Interact {
+    pub entity_id: VarInt,
+    pub action: InteractAction,
+    pub sneaking: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractAction {
+    /// Hand is 0 for main hand, 1 for off hand
+    Interact {
+        hand: VarInt,
+    },
+    Attack,
+    InteractAt {
+        target_x: f32,
+        target_y: f32,
+        target_z: f32,
+        hand: VarInt,
+    },
+}
+
+impl ser::Serialize for InteractAction {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            InteractAction::Interact { hand } => {
+                VarInt(0).serialize(writer)?;
+                hand.serialize(writer)
+            }
+            InteractAction::Attack => VarInt(1).serialize(writer),
+            InteractAction::InteractAt {
+                target_x,
+                target_y,
+                target_z,
+                hand,
+            } => {
+                VarInt(2).serialize(writer)?;
+                target_x.serialize(writer)?;
+                target_y.serialize(writer)?;
+                target_z.serialize(writer)?;
+                hand.serialize(writer)
+            }
+        }
+    }
+}
+
+impl ser::Deserialize for InteractAction {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        match VarInt::deserialize(reader)?.0 {
+            0 => Ok(InteractAction::Interact {
+                hand: VarInt::deserialize(reader)?,
+            }),
+            1 => Ok(InteractAction::Attack),
+            2 => Ok(InteractAction::InteractAt {
+                target_x: f32::deserialize(reader)?,
+                target_y: f32::deserialize(reader)?,
+                target_z: f32::deserialize(reader)?,
+                hand: VarInt::deserialize(reader)?,
+            }),
+            _ => Err(SerializationError::InvalidValue("interact action")),
+        }
+    }
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x17)]
+pub struct JigsawGenerate {
+    pub location: Position,
+    pub levels: VarInt,
+    pub keep_jigsaws: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x18)]
+pub struct KeepAlive {
+    pub keep_alive_id: i64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x19)]
+pub struct LockDifficulty {
+    pub locked: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1A)]
+pub struct SetPlayerPosition {
+    pub x: f64,
+    /// Feet position
+    pub feet_y: f64,
+    pub z: f64,
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1B)]
+pub struct SetPlayerPositionAndRotation {
+    pub x: f64,
+    pub feet_y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1C)]
+pub struct SetPlayerRotation {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1D)]
+pub struct SetPlayerOnGround {
+    pub on_ground: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1E)]
+pub struct MoveVehicle {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x1F)]
+pub struct PaddleBoat {
+    pub left_paddle_turning: bool,
+    pub right_paddle_turning: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x20)]
+pub struct PickItem {
+    pub slot_to_use: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x21)]
+pub struct PingRequest {
+    pub payload: i64,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x22)]
+pub struct PlaceRecipe {
+    pub window_id: i8,
+    pub recipe: String,
+    pub make_all: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x23)]
+pub struct PlayerAbilities {
+    /// 0x02: flying
+    pub flags: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x24)]
+pub struct PlayerAction {
+    /// 0: start digging, 1: cancel, 2: finish, 3: drop stack, 4: drop item,
+    /// 5: finish using item, 6: swap hands
+    pub status: VarInt,
+    pub location: Position,
+    pub face: i8,
+    pub sequence: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x25)]
+pub struct PlayerCommand {
+    pub entity_id: VarInt,
+    pub action_id: VarInt,
+    /// Only used by the horse jump action
+    pub jump_boost: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x26)]
+pub struct PlayerInput {
+    pub sideways: f32,
+    pub forward: f32,
+    /// 0x01: jump, 0x02: unmount
+    pub flags: u8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x27)]
+pub struct Pong {
+    pub id: i32,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x28)]
+pub struct ChangeRecipeBookSettings {
+    pub book_id: VarInt,
+    pub book_open: bool,
+    pub filter_active: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x29)]
+pub struct SetSeenRecipe {
+    pub recipe_id: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2A)]
+pub struct RenameItem {
+    pub item_name: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2B)]
+pub struct ResourcePackResponse {
+    pub uuid: u128,
+    pub result: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2C)]
+pub struct SeenAdvancements {
+    /// 0: opened tab, 1: closed screen
+    pub action: VarInt,
+    /// Present only for the opened tab action
+    pub data: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2D)]
+pub struct SelectTrade {
+    pub selected_slot: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2E)]
+pub struct SetBeaconEffect {
+    pub primary_effect: Option<VarInt>,
+    pub secondary_effect: Option<VarInt>,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x2F)]
+pub struct SetHeldItem {
+    pub slot: i16,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x30)]
+pub struct ProgramCommandBlock {
+    pub location: Position,
+    pub command: String,
+    pub mode: VarInt,
+    pub flags: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x31)]
+pub struct ProgramCommandBlockMinecart {
+    pub entity_id: VarInt,
+    pub command: String,
+    pub track_output: bool,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x32)]
+pub struct SetCreativeModeSlot {
+    pub slot: i16,
+    pub clicked_item: RestBuffer,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x33)]
+pub struct ProgramJigsawBlock {
+    pub location: Position,
+    pub name: String,
+    pub target: String,
+    pub pool: String,
+    pub final_state: String,
+    pub joint_type: String,
+    pub selection_priority: VarInt,
+    pub placement_priority: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x34)]
+pub struct ProgramStructureBlock {
+    pub location: Position,
+    pub action: VarInt,
+    pub mode: VarInt,
+    pub name: String,
+    pub offset_x: i8,
+    pub offset_y: i8,
+    pub offset_z: i8,
+    pub size_x: i8,
+    pub size_y: i8,
+    pub size_z: i8,
+    pub mirror: VarInt,
+    pub rotation: VarInt,
+    pub metadata: String,
+    pub integrity: f32,
+    pub seed: VarLong,
+    pub flags: i8,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x35)]
+pub struct UpdateSign {
+    pub location: Position,
+    pub is_front_text: bool,
+    pub line_1: String,
+    pub line_2: String,
+    pub line_3: String,
+    pub line_4: String,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x36)]
+pub struct SwingArm {
+    pub hand: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x37)]
+pub struct TeleportToEntity {
+    pub target_player: u128,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x38)]
+pub struct UseItemOn {
+    pub hand: VarInt,
+    pub location: Position,
+    pub face: VarInt,
+    pub cursor_position_x: f32,
+    pub cursor_position_y: f32,
+    pub cursor_position_z: f32,
+    pub inside_block: bool,
+    pub sequence: VarInt,
+}
+
+#[derive(Packet, Debug, Clone, PartialEq)]
+#[packet(0x39)]
+pub struct UseItem {
+    pub hand: VarInt,
+    pub sequence: VarInt,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+play_packets! {
+    /// Any serverbound Play packet.
+    ServerboundPlayPacket {
+        ConfirmTeleportation,
+        QueryBlockEntityTag,
+        ChangeDifficulty,
+        AcknowledgeMessage,
+        ChatCommand,
+        SignedChatCommand,
+        ChatMessage,
+        PlayerSession,
+        ChunkBatchReceived,
+        ClientStatus,
+        ClientInformation,
+        CommandSuggestionsRequest,
+        AcknowledgeConfiguration,
+        ClickContainerButton,
+        ClickContainer,
+        CloseContainer,
+        ChangeContainerSlotState,
+        CookieResponse,
+        PluginMessage,
+        DebugSampleSubscription,
+        EditBook,
+        QueryEntityTag,
+        Interact,
+        JigsawGenerate,
+        KeepAlive,
+        LockDifficulty,
+        SetPlayerPosition,
+        SetPlayerPositionAndRotation,
+        SetPlayerRotation,
+        SetPlayerOnGround,
+        MoveVehicle,
+        PaddleBoat,
+        PickItem,
+        PingRequest,
+        PlaceRecipe,
+        PlayerAbilities,
+        PlayerAction,
+        PlayerCommand,
+        PlayerInput,
+        Pong,
+        ChangeRecipeBookSettings,
+        SetSeenRecipe,
+        RenameItem,
+        ResourcePackResponse,
+        SeenAdvancements,
+        SelectTrade,
+        SetBeaconEffect,
+        SetHeldItem,
+        ProgramCommandBlock,
+        ProgramCommandBlockMinecart,
+        SetCreativeModeSlot,
+        ProgramJigsawBlock,
+        ProgramStructureBlock,
+        UpdateSign,
+        SwingArm,
+        TeleportToEntity,
+        UseItemOn,
+        UseItem,
+    }
+}
@@ -0,0 +1,30 @@
+pub mod serverbound {
+    use crate::Packet;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x00)]
+    pub struct StatusRequest;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x01)]
+    pub struct PingRequest {
+        pub payload: i64,
+    }
+}
+
+pub mod clientbound {
+    use crate::Packet;
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x00)]
+    pub struct StatusResponse {
+        /// Server list JSON
+        pub json_response: String,
+    }
+
+    #[derive(Packet, Debug, Clone, PartialEq)]
+    #[packet(0x01)]
+    pub struct PongResponse {
+        pub payload: i64,
+    }
+}
@@ -0,0 +1,262 @@
+//! Player profiles: the name, UUID and properties carried by the session
+//! server, Login Success and Player Info Update.
+
+use std::io::{Read, Write};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use md5::{Digest, Md5};
+use thiserror::Error;
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+use crate::encryption::{EncryptionError, verify_rsa_sha1};
+use crate::{
+    packets::{login::clientbound::LoginSuccess, play::clientbound::AddPlayer},
+    ser::{Deserialize, PrefixedArray, SerializationError, Serialize},
+    varint::VarInt,
+};
+
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("Invalid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    #[error("Invalid textures JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Property {0} is not signed")]
+    MissingSignature(String),
+
+    #[error("Invalid signature for property {0}")]
+    InvalidSignature(String),
+
+    #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
+}
+
+/// Mojang's JSON form: `id` is the UUID as 32 hex digits without dashes.
+/// On the wire it is a UUID, a string and a prefixed array of properties.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GameProfile {
+    #[serde(with = "uuid_hex")]
+    pub id: u128,
+    pub name: String,
+    #[serde(default)]
+    pub properties: Vec<ProfileProperty>,
+}
+
+/// Usually `textures`: base64 JSON, signed by Yggdrasil when requested.
+/// The same type as the Login Success property.
+pub use crate::packets::login::clientbound::Property as ProfileProperty;
+
+impl GameProfile {
+    pub fn new(id: u128, name: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            properties: Vec::new(),
+        }
+    }
+
+    /// The profile an offline-mode server gives `name`, see [`offline_uuid`].
+    pub fn offline(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self::new(offline_uuid(&name), name)
+    }
+
+    pub fn property(&self, name: &str) -> Option<&ProfileProperty> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name)
+    }
+
+    /// Decodes the `textures` property, `None` if there is none.
+    pub fn textures(&self) -> Result<Option<Textures>, ProfileError> {
+        self.property(Textures::PROPERTY)
+            .map(ProfileProperty::decode_textures)
+            .transpose()
+    }
+
+    /// Checks that every property is signed by `public_key_der`.
+    #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+    pub fn verify_signatures(&self, public_key_der: &[u8]) -> Result<(), ProfileError> {
+        for property in &self.properties {
+            property.verify_signature(public_key_der)?;
+        }
+        Ok(())
+    }
+
+    /// With `strict_error_handling` off, like vanilla servers.
+    pub fn login_success(&self) -> LoginSuccess {
+        LoginSuccess {
+            uuid: self.id,
+            username: self.name.clone(),
+            properties: PrefixedArray(self.properties.clone()),
+            strict_error_handling: false,
+        }
+    }
+
+    pub fn from_add_player(uuid: u128, add_player: &AddPlayer) -> Self {
+        Self {
+            id: uuid,
+            name: add_player.name.clone(),
+            properties: add_player.properties.0.clone(),
+        }
+    }
+}
+
+impl ProfileProperty {
+    pub fn decode_textures(&self) -> Result<Textures, ProfileError> {
+        let json = STANDARD.decode(&self.value)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// Checks the Yggdrasil signature (RSA-SHA1 over the base64 `value`)
+    /// against a DER-encoded public key. Needs a crypto backend feature.
+    #[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+    pub fn verify_signature(&self, public_key_der: &[u8]) -> Result<(), ProfileError> {
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| ProfileError::MissingSignature(self.name.clone()))?;
+        let signature = STANDARD.decode(signature)?;
+
+        if !verify_rsa_sha1(public_key_der, self.value.as_bytes(), &signature)? {
+            return Err(ProfileError::InvalidSignature(self.name.clone()));
+        }
+        Ok(())
+    }
+}
+
+impl From<&LoginSuccess> for GameProfile {
+    fn from(login_success: &LoginSuccess) -> Self {
+        Self {
+            id: login_success.uuid,
+            name: login_success.username.clone(),
+            properties: login_success.properties.0.clone(),
+        }
+    }
+}
+
+impl Serialize for GameProfile {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        self.id.serialize(writer)?;
+        self.name.serialize(writer)?;
+        VarInt(self.properties.len() as i32).serialize(writer)?;
+        for property in &self.properties {
+            property.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Deserialize for GameProfile {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
+        Ok(Self {
+            id: u128::deserialize(reader)?,
+            name: String::deserialize(reader)?,
+            properties: PrefixedArray::deserialize(reader)?.0,
+        })
+    }
+}
+
+/// Decoded `textures` property.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Textures {
+    /// Milliseconds since the epoch
+    pub timestamp: i64,
+    #[serde(with = "uuid_hex")]
+    pub profile_id: u128,
+    pub profile_name: String,
+    #[serde(default)]
+    pub signature_required: bool,
+    #[serde(rename = "textures", default)]
+    urls: TextureUrls,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+struct TextureUrls {
+    #[serde(rename = "SKIN")]
+    skin: Option<Texture>,
+    #[serde(rename = "CAPE")]
+    cape: Option<Texture>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+struct Texture {
+    url: String,
+    #[serde(default)]
+    metadata: TextureMetadata,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+struct TextureMetadata {
+    #[serde(default)]
+    model: SkinModel,
+}
+
+/// Arms model. Missing metadata means classic.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SkinModel {
+    #[default]
+    #[serde(alias = "default")]
+    Classic,
+    Slim,
+}
+
+impl Textures {
+    pub const PROPERTY: &str = "textures";
+
+    pub fn skin_url(&self) -> Option<&str> {
+        self.urls.skin.as_ref().map(|skin| skin.url.as_str())
+    }
+
+    pub fn skin_model(&self) -> SkinModel {
+        self.urls
+            .skin
+            .as_ref()
+            .map(|skin| skin.metadata.model)
+            .unwrap_or_default()
+    }
+
+    pub fn cape_url(&self) -> Option<&str> {
+        self.urls.cape.as_ref().map(|cape| cape.url.as_str())
+    }
+}
+
+/// Version 3 UUID of `OfflinePlayer:<name>`, used by offline-mode servers.
+pub fn offline_uuid(name: &str) -> u128 {
+    let mut hash: [u8; 16] = Md5::digest(format!("OfflinePlayer:{name}")).into();
+    hash[6] = hash[6] & 0x0F | 0x30;
+    hash[8] = hash[8] & 0x3F | 0x80;
+    u128::from_be_bytes(hash)
+}
+
+/// 32 lowercase hex digits, as used by the Mojang APIs.
+pub fn uuid_to_hex(uuid: u128) -> String {
+    format!("{uuid:032x}")
+}
+
+/// Accepts the plain and the dashed form.
+pub fn uuid_from_hex(hex: &str) -> Option<u128> {
+    let digits: String = hex.chars().filter(|c| *c != '-').collect();
+    if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u128::from_str_radix(&digits, 16).ok()
+}
+
+mod uuid_hex {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(uuid: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::uuid_to_hex(*uuid))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        super::uuid_from_hex(&hex).ok_or_else(|| D::Error::custom(format!("invalid UUID {hex}")))
+    }
+}
@@ -0,0 +1,523 @@
+//! GameSpy4 Query over UDP, enabled by `enable-query` in server.properties.
+//! Unlike the status ping, full stat lists every online player.
+//!
+//! Every request starts with `FE FD`, a type byte and a session ID. The client
+//! first asks for a challenge token (type 9), then sends it with a stat
+//! request (type 0). Four extra padding bytes ask for the full stat instead
+//! of the basic one.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), minecraft_protocol::query::QueryError> {
+//! use minecraft_protocol::query::QueryClient;
+//!
+//! let client = QueryClient::connect("example.com:25565").await?;
+//! let stat = client.full_stat().await?;
+//! println!("{} players: {:?}", stat.online_players, stat.players);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+use tokio::{
+    net::{ToSocketAddrs, UdpSocket, lookup_host},
+    time::timeout,
+};
+
+const MAGIC: [u8; 2] = [0xFE, 0xFD];
+const HANDSHAKE: u8 = 9;
+const STAT: u8 = 0;
+const FULL_STAT_PADDING: [u8; 4] = [0; 4];
+const KEY_VALUE_START: &[u8] = b"splitnum\0\x80\0";
+const PLAYERS_START: &[u8] = b"\x01player_\0\0";
+/// Session IDs only keep the low four bits of each byte
+const SESSION_ID_MASK: i32 = 0x0F0F_0F0F;
+/// Vanilla servers rotate challenge tokens every 30 seconds
+const CHALLENGE_LIFETIME: Duration = Duration::from_secs(30);
+/// Keeps spoofed handshakes from growing the challenge map without bound
+const MAX_CHALLENGES: usize = 4096;
+const MAX_DATAGRAM: usize = 65_535;
+
+#[derive(Debug, Error)]
+pub enum QueryError {
+    #[error("IO Error: {0}")]
+    IOError(#[from] io::Error),
+
+    #[error("Random generation failed: {0}")]
+    Random(#[from] getrandom::Error),
+
+    #[error("Timed out")]
+    Timeout,
+
+    #[error("Invalid query packet: {0}")]
+    InvalidPacket(&'static str),
+}
+
+/// Basic stat: what the status ping shows, without the player list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicStat {
+    pub motd: String,
+    pub game_type: String,
+    pub map: String,
+    pub online_players: i32,
+    pub max_players: i32,
+    pub host_port: u16,
+    pub host_ip: String,
+}
+
+/// Full stat. Keys other than the vanilla ones end up in `extra`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullStat {
+    pub hostname: String,
+    /// Always `SMP` on vanilla
+    pub game_type: String,
+    /// Always `MINECRAFT` on vanilla
+    pub game_id: String,
+    pub version: String,
+    /// `Server mod: Plugin1; Plugin2`, empty on vanilla
+    pub plugins: String,
+    pub map: String,
+    pub online_players: i32,
+    pub max_players: i32,
+    pub host_port: u16,
+    pub host_ip: String,
+    pub extra: Vec<(String, String)>,
+    pub players: Vec<String>,
+}
+
+impl Default for FullStat {
+    fn default() -> Self {
+        Self {
+            hostname: "A Minecraft Server".to_string(),
+            game_type: "SMP".to_string(),
+            game_id: "MINECRAFT".to_string(),
+            version: String::new(),
+            plugins: String::new(),
+            map: "world".to_string(),
+            online_players: 0,
+            max_players: 20,
+            host_port: 25565,
+            host_ip: "0.0.0.0".to_string(),
+            extra: Vec::new(),
+            players: Vec::new(),
+        }
+    }
+}
+
+impl From<&FullStat> for BasicStat {
+    fn from(stat: &FullStat) -> Self {
+        Self {
+            motd: stat.hostname.clone(),
+            game_type: stat.game_type.clone(),
+            map: stat.map.clone(),
+            online_players: stat.online_players,
+            max_players: stat.max_players,
+            host_port: stat.host_port,
+            host_ip: stat.host_ip.clone(),
+        }
+    }
+}
+
+impl BasicStat {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        for value in [&self.motd, &self.game_type, &self.map] {
+            write_string(buf, value);
+        }
+        write_string(buf, &self.online_players.to_string());
+        write_string(buf, &self.max_players.to_string());
+        // The only little-endian field of the protocol
+        buf.extend_from_slice(&self.host_port.to_le_bytes());
+        write_string(buf, &self.host_ip);
+    }
+
+    fn decode(mut data: &[u8]) -> Result<Self, QueryError> {
+        let data = &mut data;
+        let motd = read_string(data)?;
+        let game_type = read_string(data)?;
+        let map = read_string(data)?;
+        let online_players = parse_number(&read_string(data)?)?;
+        let max_players = parse_number(&read_string(data)?)?;
+        let Some((port, rest)) = data.split_first_chunk::<2>() else {
+            return Err(QueryError::InvalidPacket("truncated basic stat"));
+        };
+        *data = rest;
+
+        Ok(Self {
+            motd,
+            game_type,
+            map,
+            online_players,
+            max_players,
+            host_port: u16::from_le_bytes(*port),
+            host_ip: read_string(data)?,
+        })
+    }
+}
+
+impl FullStat {
+    /// The value of `key` as sent, vanilla keys included.
+    pub fn get(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "hostname" => self.hostname.clone(),
+            "gametype" => self.game_type.clone(),
+            "game_id" => self.game_id.clone(),
+            "version" => self.version.clone(),
+            "plugins" => self.plugins.clone(),
+            "map" => self.map.clone(),
+            "numplayers" => self.online_players.to_string(),
+            "maxplayers" => self.max_players.to_string(),
+            "hostport" => self.host_port.to_string(),
+            "hostip" => self.host_ip.clone(),
+            _ => {
+                return self
+                    .extra
+                    .iter()
+                    .find(|(extra, _)| extra == key)
+                    .map(|(_, value)| value.clone());
+            }
+        })
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(KEY_VALUE_START);
+        let vanilla = [
+            "hostname",
+            "gametype",
+            "game_id",
+            "version",
+            "plugins",
+            "map",
+            "numplayers",
+            "maxplayers",
+            "hostport",
+            "hostip",
+        ];
+        for key in vanilla {
+            write_string(buf, key);
+            write_string(buf, &self.get(key).unwrap_or_default());
+        }
+        for (key, value) in &self.extra {
+            write_string(buf, key);
+            write_string(buf, value);
+        }
+        buf.push(0);
+
+        buf.extend_from_slice(PLAYERS_START);
+        for player in &self.players {
+            write_string(buf, player);
+        }
+        buf.push(0);
+    }
+
+    fn decode(mut data: &[u8]) -> Result<Self, QueryError> {
+        let data = &mut data;
+        *data = data
+            .strip_prefix(KEY_VALUE_START)
+            .ok_or(QueryError::InvalidPacket("missing full stat padding"))?;
+
+        let mut stat = Self {
+            hostname: String::new(),
+            game_type: String::new(),
+            game_id: String::new(),
+            version: String::new(),
+            plugins: String::new(),
+            map: String::new(),
+            online_players: 0,
+            max_players: 0,
+            host_port: 0,
+            host_ip: String::new(),
+            extra: Vec::new(),
+            players: Vec::new(),
+        };
+        loop {
+            let key = read_string(data)?;
+            if key.is_empty() {
+                break;
+            }
+            let value = read_string(data)?;
+            match key.as_str() {
+                "hostname" => stat.hostname = value,
+                "gametype" => stat.game_type = value,
+                "game_id" => stat.game_id = value,
+                "version" => stat.version = value,
+                "plugins" => stat.plugins = value,
+                "map" => stat.map = value,
+                "numplayers" => stat.online_players = parse_number(&value)?,
+                "maxplayers" => stat.max_players = parse_number(&value)?,
+                "hostport" => stat.host_port = parse_number(&value)?,
+                "hostip" => stat.host_ip = value,
+                _ => stat.extra.push((key, value)),
+            }
+        }
+
+        *data = data
+            .strip_prefix(PLAYERS_START)
+            .ok_or(QueryError::InvalidPacket("missing player section"))?;
+        loop {
+            let player = read_string(data)?;
+            if player.is_empty() {
+                break;
+            }
+            stat.players.push(player);
+        }
+
+        Ok(stat)
+    }
+}
+
+/// Queries one server. Each stat request fetches a fresh challenge token
+/// first, so a client can be kept around indefinitely.
+#[derive(Debug)]
+pub struct QueryClient {
+    socket: UdpSocket,
+    session_id: i32,
+    timeout: Duration,
+}
+
+impl QueryClient {
+    /// Binds a local socket and connects it to `address`, the server's
+    /// `query.port`.
+    pub async fn connect(address: impl ToSocketAddrs) -> Result<Self, QueryError> {
+        let address = lookup_host(address)
+            .await?
+            .next()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::AddrNotAvailable))?;
+        let local: SocketAddr = if address.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+
+        let socket = UdpSocket::bind(local).await?;
+        socket.connect(address).await?;
+        Ok(Self {
+            socket,
+            session_id: i32::from_be_bytes(random_bytes()?) & SESSION_ID_MASK,
+            timeout: Duration::from_secs(5),
+        })
+    }
+
+    /// Limit for each request and its response, 5 seconds by default. Lost
+    /// datagrams are not retried.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn session_id(&self) -> i32 {
+        self.session_id
+    }
+
+    /// Asks for a challenge token, valid for about 30 seconds.
+    pub async fn handshake(&self) -> Result<i32, QueryError> {
+        let response = self.request(HANDSHAKE, &[]).await?;
+        let mut data = response.as_slice();
+        parse_number(&read_string(&mut data)?)
+    }
+
+    pub async fn basic_stat(&self) -> Result<BasicStat, QueryError> {
+        let token = self.handshake().await?;
+        let response = self.request(STAT, &token.to_be_bytes()).await?;
+        BasicStat::decode(&response)
+    }
+
+    pub async fn full_stat(&self) -> Result<FullStat, QueryError> {
+        let token = self.handshake().await?;
+        let mut payload = token.to_be_bytes().to_vec();
+        payload.extend_from_slice(&FULL_STAT_PADDING);
+        let response = self.request(STAT, &payload).await?;
+        FullStat::decode(&response)
+    }
+
+    /// Sends one request and returns the response after its type and
+    /// session ID.
+    async fn request(&self, kind: u8, payload: &[u8]) -> Result<Vec<u8>, QueryError> {
+        let mut request = MAGIC.to_vec();
+        request.push(kind);
+        request.extend_from_slice(&self.session_id.to_be_bytes());
+        request.extend_from_slice(payload);
+
+        timeout(self.timeout, async {
+            self.socket.send(&request).await?;
+            let mut buf = vec![0; MAX_DATAGRAM];
+            let len = self.socket.recv(&mut buf).await?;
+            buf.truncate(len);
+
+            if buf.len() < 5 || buf[0] != kind {
+                return Err(QueryError::InvalidPacket("unexpected response type"));
+            }
+            if buf[1..5] != self.session_id.to_be_bytes() {
+                return Err(QueryError::InvalidPacket("session ID mismatch"));
+            }
+            buf.drain(..5);
+            Ok(buf)
+        })
+        .await
+        .map_err(|_| QueryError::Timeout)?
+    }
+}
+
+/// Answers query requests with a [`FullStat`] that can be updated between
+/// requests.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), minecraft_protocol::query::QueryError> {
+/// use minecraft_protocol::query::{FullStat, QueryServer};
+///
+/// let mut server = QueryServer::bind("0.0.0.0:25565", FullStat::default()).await?;
+/// loop {
+///     if let Err(e) = server.handle_next().await {
+///         eprintln!("query: {e}");
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct QueryServer {
+    socket: UdpSocket,
+    stat: FullStat,
+    challenges: HashMap<SocketAddr, (i32, Instant)>,
+    expired_at: Instant,
+}
+
+impl QueryServer {
+    pub async fn bind(address: impl ToSocketAddrs, stat: FullStat) -> Result<Self, QueryError> {
+        Ok(Self {
+            socket: UdpSocket::bind(address).await?,
+            stat,
+            challenges: HashMap::new(),
+            expired_at: Instant::now(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, QueryError> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    pub fn stat(&self) -> &FullStat {
+        &self.stat
+    }
+
+    pub fn stat_mut(&mut self) -> &mut FullStat {
+        &mut self.stat
+    }
+
+    /// Waits for one datagram and answers it. Malformed requests and stat
+    /// requests with an unknown or expired token get no answer and return
+    /// [`QueryError::InvalidPacket`].
+    pub async fn handle_next(&mut self) -> Result<(), QueryError> {
+        let mut buf = vec![0; MAX_DATAGRAM];
+        // Expired tokens are dropped every CHALLENGE_LIFETIME, even when idle
+        let (len, peer) = loop {
+            let next_expiry = CHALLENGE_LIFETIME.saturating_sub(self.expired_at.elapsed());
+            match timeout(next_expiry, self.socket.recv_from(&mut buf)).await {
+                Ok(received) => break received?,
+                Err(_) => self.expire_challenges(),
+            }
+        };
+        let request = &buf[..len];
+
+        let Some(([kind, session @ ..], payload)) = request
+            .strip_prefix(&MAGIC)
+            .and_then(|rest| rest.split_first_chunk::<5>())
+        else {
+            return Err(QueryError::InvalidPacket("missing magic"));
+        };
+
+        let mut response = vec![*kind];
+        response.extend_from_slice(session);
+
+        match *kind {
+            HANDSHAKE => {
+                let token = self.challenge(peer)?;
+                write_string(&mut response, &token.to_string());
+            }
+            STAT => {
+                let Some((token, padding)) = payload.split_first_chunk::<4>() else {
+                    return Err(QueryError::InvalidPacket("missing challenge token"));
+                };
+                if !self.check_challenge(peer, i32::from_be_bytes(*token)) {
+                    return Err(QueryError::InvalidPacket("invalid challenge token"));
+                }
+                match padding.len() {
+                    0 => BasicStat::from(&self.stat).encode(&mut response),
+                    4 => self.stat.encode(&mut response),
+                    _ => return Err(QueryError::InvalidPacket("unexpected stat length")),
+                }
+            }
+            _ => return Err(QueryError::InvalidPacket("unknown request type")),
+        }
+
+        self.socket.send_to(&response, peer).await?;
+        Ok(())
+    }
+
+    /// A new token for `peer`. Once [`MAX_CHALLENGES`] peers hold one, the
+    /// oldest token is dropped.
+    fn challenge(&mut self, peer: SocketAddr) -> Result<i32, QueryError> {
+        if self.challenges.len() >= MAX_CHALLENGES && !self.challenges.contains_key(&peer) {
+            let oldest = self
+                .challenges
+                .iter()
+                .min_by_key(|(_, (_, issued))| *issued)
+                .map(|(peer, _)| *peer);
+            if let Some(oldest) = oldest {
+                self.challenges.remove(&oldest);
+            }
+        }
+
+        let token = i32::from_be_bytes(random_bytes()?);
+        self.challenges.insert(peer, (token, Instant::now()));
+        Ok(token)
+    }
+
+    fn expire_challenges(&mut self) {
+        let now = Instant::now();
+        self.challenges
+            .retain(|_, (_, issued)| now.duration_since(*issued) < CHALLENGE_LIFETIME);
+        self.expired_at = now;
+    }
+
+    fn check_challenge(&self, peer: SocketAddr, token: i32) -> bool {
+        self.challenges
+            .get(&peer)
+            .is_some_and(|(expected, issued)| {
+                *expected == token && issued.elapsed() < CHALLENGE_LIFETIME
+            })
+    }
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], QueryError> {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn write_string(buf: &mut Vec<u8>, value: &str) {
+    buf.extend_from_slice(value.as_bytes());
+    buf.push(0);
+}
+
+/// Null-terminated. Invalid UTF-8 is replaced, some servers send Latin-1.
+fn read_string(data: &mut &[u8]) -> Result<String, QueryError> {
+    let end = data
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or(QueryError::InvalidPacket("unterminated string"))?;
+    let value = String::from_utf8_lossy(&data[..end]).into_owned();
+    *data = &data[end + 1..];
+    Ok(value)
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, QueryError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| QueryError::InvalidPacket("invalid number"))
+}
@@ -0,0 +1,359 @@
+//! Remote console with Source RCON framing, enabled by `enable-rcon` in
+//! server.properties.
+//!
+//! Each packet is a little-endian `i32` length, then request ID, type, the
+//! body and two null bytes. Long responses arrive in several packets with the
+//! same request ID; the client follows each command with an empty packet
+//! and concatenates everything until that one is answered.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), minecraft_protocol::rcon::RconError> {
+//! use minecraft_protocol::rcon::RconClient;
+//!
+//! let mut client = RconClient::connect("example.com:25575", "hunter2").await?;
+//! println!("{}", client.command("list").await?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{borrow::Cow, io, net::SocketAddr};
+
+use thiserror::Error;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+/// Longest command vanilla servers accept.
+pub const MAX_COMMAND_LENGTH: usize = 1446;
+/// Vanilla splits responses into bodies of at most this many bytes.
+pub const MAX_RESPONSE_CHUNK: usize = 4096;
+/// Request ID plus type plus the two null bytes
+const MIN_PACKET_LENGTH: i32 = 10;
+const MAX_PACKET_LENGTH: i32 = 1 << 20;
+/// Request ID of an Auth Response for a wrong password
+const AUTH_FAILED_ID: i32 = -1;
+
+#[derive(Debug, Error)]
+pub enum RconError {
+    #[error("IO Error: {0}")]
+    IOError(#[from] io::Error),
+
+    #[error("Invalid RCON packet: {0}")]
+    InvalidPacket(&'static str),
+
+    #[error("Wrong RCON password")]
+    AuthFailed,
+
+    #[error("Command is {0} bytes long, the limit is {MAX_COMMAND_LENGTH}")]
+    CommandTooLong(usize),
+
+    #[error("Response for request {actual}, expected {expected}")]
+    UnexpectedRequestId { expected: i32, actual: i32 },
+
+    #[error("Connection closed")]
+    Closed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RconPacket {
+    pub request_id: i32,
+    /// One of the `RconPacket` type constants. [`RconPacket::EXEC_COMMAND`]
+    /// and [`RconPacket::AUTH_RESPONSE`] share a value.
+    pub kind: i32,
+    /// Usually UTF-8, but responses may be split mid-character.
+    pub body: Vec<u8>,
+}
+
+impl RconPacket {
+    pub const RESPONSE_VALUE: i32 = 0;
+    pub const EXEC_COMMAND: i32 = 2;
+    pub const AUTH_RESPONSE: i32 = 2;
+    pub const AUTH: i32 = 3;
+
+    pub fn new(request_id: i32, kind: i32, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            request_id,
+            kind,
+            body: body.into(),
+        }
+    }
+
+    pub fn body_text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+
+    /// Returns [`RconError::Closed`] if the stream ends between packets.
+    pub async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Self, RconError> {
+        let mut length = [0; 4];
+        match reader.read_exact(&mut length).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Err(RconError::Closed),
+            Err(e) => return Err(e.into()),
+        }
+        let length = i32::from_le_bytes(length);
+        if !(MIN_PACKET_LENGTH..=MAX_PACKET_LENGTH).contains(&length) {
+            return Err(RconError::InvalidPacket("length out of range"));
+        }
+
+        let mut data = vec![0; length as usize];
+        reader.read_exact(&mut data).await?;
+
+        let request_id = i32::from_le_bytes(data[0..4].try_into().unwrap());
+        let kind = i32::from_le_bytes(data[4..8].try_into().unwrap());
+        // Body is null-terminated, followed by one more null byte
+        let mut body = data.split_off(8);
+        if body.pop() != Some(0) {
+            return Err(RconError::InvalidPacket("missing padding"));
+        }
+        if let Some(end) = body.iter().position(|byte| *byte == 0) {
+            body.truncate(end);
+        }
+
+        Ok(Self {
+            request_id,
+            kind,
+            body,
+        })
+    }
+
+    pub async fn write<W: AsyncWrite + Unpin>(&self, writer: &mut W) -> Result<(), RconError> {
+        let length = MIN_PACKET_LENGTH + self.body.len() as i32;
+
+        let mut buf = Vec::with_capacity(4 + length as usize);
+        buf.extend_from_slice(&length.to_le_bytes());
+        buf.extend_from_slice(&self.request_id.to_le_bytes());
+        buf.extend_from_slice(&self.kind.to_le_bytes());
+        buf.extend_from_slice(&self.body);
+        buf.extend_from_slice(&[0, 0]);
+
+        writer.write_all(&buf).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+}
+
+/// An authenticated RCON connection.
+#[derive(Debug)]
+pub struct RconClient<S> {
+    stream: S,
+    next_id: i32,
+}
+
+impl RconClient<TcpStream> {
+    pub async fn connect(address: impl ToSocketAddrs, password: &str) -> Result<Self, RconError> {
+        let stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        Self::authenticate(stream, password).await
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> RconClient<S> {
+    /// Logs in over an already connected stream.
+    pub async fn authenticate(stream: S, password: &str) -> Result<Self, RconError> {
+        let mut client = Self { stream, next_id: 1 };
+        let id = client.next_id();
+        RconPacket::new(id, RconPacket::AUTH, password)
+            .write(&mut client.stream)
+            .await?;
+
+        // Source servers send an empty Response Value first
+        loop {
+            let packet = RconPacket::read(&mut client.stream).await?;
+            if packet.kind != RconPacket::AUTH_RESPONSE {
+                continue;
+            }
+            return match packet.request_id {
+                AUTH_FAILED_ID => Err(RconError::AuthFailed),
+                actual if actual == id => Ok(client),
+                actual => Err(RconError::UnexpectedRequestId {
+                    expected: id,
+                    actual,
+                }),
+            };
+        }
+    }
+
+    /// Runs `command` and returns its output, joined from all response
+    /// packets.
+    pub async fn command(&mut self, command: &str) -> Result<String, RconError> {
+        if command.len() > MAX_COMMAND_LENGTH {
+            return Err(RconError::CommandTooLong(command.len()));
+        }
+
+        let id = self.next_id();
+        let sentinel = self.next_id();
+        RconPacket::new(id, RconPacket::EXEC_COMMAND, command)
+            .write(&mut self.stream)
+            .await?;
+        // Answered only after the command, whatever the server makes of it
+        RconPacket::new(sentinel, RconPacket::RESPONSE_VALUE, "")
+            .write(&mut self.stream)
+            .await?;
+
+        let mut output = Vec::new();
+        loop {
+            let packet = RconPacket::read(&mut self.stream).await?;
+            match packet.request_id {
+                actual if actual == id => output.extend_from_slice(&packet.body),
+                actual if actual == sentinel => break,
+                actual => {
+                    return Err(RconError::UnexpectedRequestId {
+                        expected: id,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /// Positive and never [`AUTH_FAILED_ID`].
+    fn next_id(&mut self) -> i32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.checked_add(1).unwrap_or(1);
+        id
+    }
+}
+
+/// Accepts RCON connections and checks the password.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), minecraft_protocol::rcon::RconError> {
+/// use minecraft_protocol::rcon::RconServer;
+///
+/// let server = RconServer::bind("127.0.0.1:25575", "hunter2").await?;
+/// let (mut session, _) = server.accept().await?;
+/// while let Some(request) = session.next_command().await? {
+///     session.respond(&request, &format!("ran {}", request.command)).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RconServer {
+    listener: TcpListener,
+    password: String,
+}
+
+impl RconServer {
+    pub async fn bind(
+        address: impl ToSocketAddrs,
+        password: impl Into<String>,
+    ) -> Result<Self, RconError> {
+        Ok(Self {
+            listener: TcpListener::bind(address).await?,
+            password: password.into(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, RconError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Waits for a client and authenticates it. A wrong password fails with
+    /// [`RconError::AuthFailed`] after telling the client.
+    pub async fn accept(&self) -> Result<(RconSession<TcpStream>, SocketAddr), RconError> {
+        let (stream, peer) = self.listener.accept().await?;
+        stream.set_nodelay(true)?;
+        Ok((
+            RconSession::authenticate(stream, &self.password).await?,
+            peer,
+        ))
+    }
+}
+
+/// A command sent by an authenticated client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RconRequest {
+    pub request_id: i32,
+    pub command: String,
+}
+
+/// Server side of one authenticated connection.
+#[derive(Debug)]
+pub struct RconSession<S> {
+    stream: S,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> RconSession<S> {
+    /// Expects an Auth packet with `password`, like vanilla servers.
+    pub async fn authenticate(mut stream: S, password: &str) -> Result<Self, RconError> {
+        let auth = RconPacket::read(&mut stream).await?;
+        if auth.kind != RconPacket::AUTH {
+            return Err(RconError::InvalidPacket("expected Auth"));
+        }
+
+        if auth.body != password.as_bytes() {
+            RconPacket::new(AUTH_FAILED_ID, RconPacket::AUTH_RESPONSE, "")
+                .write(&mut stream)
+                .await?;
+            return Err(RconError::AuthFailed);
+        }
+        RconPacket::new(auth.request_id, RconPacket::AUTH_RESPONSE, "")
+            .write(&mut stream)
+            .await?;
+        Ok(Self { stream })
+    }
+
+    /// The next command, `None` once the client disconnects. Other packets
+    /// are answered with `Unknown request`, as vanilla does.
+    pub async fn next_command(&mut self) -> Result<Option<RconRequest>, RconError> {
+        loop {
+            let packet = match RconPacket::read(&mut self.stream).await {
+                Ok(packet) => packet,
+                Err(RconError::Closed) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+
+            if packet.kind == RconPacket::EXEC_COMMAND {
+                return Ok(Some(RconRequest {
+                    request_id: packet.request_id,
+                    command: packet.body_text().into_owned(),
+                }));
+            }
+            let unknown = format!("Unknown request {:x}", packet.kind);
+            RconPacket::new(packet.request_id, RconPacket::RESPONSE_VALUE, unknown)
+                .write(&mut self.stream)
+                .await?;
+        }
+    }
+
+    /// Sends `output`, split into [`MAX_RESPONSE_CHUNK`] byte packets on
+    /// character boundaries.
+    pub async fn respond(&mut self, request: &RconRequest, output: &str) -> Result<(), RconError> {
+        let mut rest = output;
+        loop {
+            let mut end = rest.len().min(MAX_RESPONSE_CHUNK);
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+            let (chunk, tail) = rest.split_at(end);
+            RconPacket::new(request.request_id, RconPacket::RESPONSE_VALUE, chunk)
+                .write(&mut self.stream)
+                .await?;
+
+            rest = tail;
+            if rest.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
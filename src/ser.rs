@@ -7,7 +7,7 @@ use thiserror::Error;
 
 use crate::{
     num::Integer,
-    varint::{VarInt, VarIntError},
+    varint::{VarInt, VarIntError, VarLong},
 };
 
 #[derive(Debug, Error)]
@@ -18,6 +18,16 @@ pub enum SerializationError {
     IOError(#[from] io::Error),
     #[error("String serialization error")]
     FromUtf8Error(#[from] FromUtf8Error),
+    #[error("Packet is not defined for protocol version {0}")]
+    UnsupportedVersion(i32),
+    #[error("Invalid NBT: {0}")]
+    InvalidNbt(&'static str),
+    #[error("{0} unread bytes at the end of the packet")]
+    TrailingBytes(usize),
+    #[error("Missing value for {0}")]
+    MissingField(&'static str),
+    #[error("Invalid value for {0}")]
+    InvalidValue(&'static str),
 }
 
 pub trait Serialize {
@@ -42,6 +52,33 @@ impl Deserialize for VarInt {
     }
 }
 
+impl Serialize for VarLong {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        Ok(self.write_sync(writer)?)
+    }
+}
+
+impl Deserialize for VarLong {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<VarLong, SerializationError> {
+        Ok(VarLong::read_sync(reader)?)
+    }
+}
+
+fn read_prefixed_bytes<R: Read + Unpin>(reader: &mut R) -> Result<Vec<u8>, SerializationError> {
+    let len = VarInt::read_sync(reader)?;
+    if len.0 < 0 {
+        return Err(VarIntError::NegativeValue.into());
+    }
+
+    // take() instead of vec![0; len] so a bogus length can't allocate gigabytes up front
+    let mut buf = Vec::new();
+    if reader.take(len.0 as u64).read_to_end(&mut buf)? != len.0 as usize {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    Ok(buf)
+}
+
 impl Serialize for String {
     fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
         VarInt(self.len() as i32).write_sync(writer)?;
@@ -52,9 +89,7 @@ impl Serialize for String {
 
 impl Deserialize for String {
     fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError> {
-        let len = VarInt::read_sync(reader)?.0 as usize;
-        let mut stream = vec![0; len];
-        reader.read_exact(&mut stream)?;
+        let stream = read_prefixed_bytes(reader)?;
 
         Ok(String::from_utf8(stream)?)
     }
@@ -113,7 +148,7 @@ impl Deserialize for bool {
 impl Serialize for Vec<u8> {
     fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
         VarInt(self.len() as i32).write_sync(writer)?;
-        Ok(writer.write_all(&self)?)
+        Ok(writer.write_all(self)?)
     }
 }
 
@@ -122,11 +157,7 @@ impl Deserialize for Vec<u8> {
     where
         Self: Sized,
     {
-        let len = VarInt::read_sync(reader)?;
-        let mut buf: Vec<u8> = vec![0; len.0 as usize];
-        reader.read_exact(&mut buf)?;
-
-        Ok(buf)
+        read_prefixed_bytes(reader)
     }
 }
 
@@ -137,3 +168,120 @@ impl Serialize for [u8] {
         Ok(())
     }
 }
+
+/// Fixed-size byte array without a length prefix, e.g. message signatures.
+impl<const N: usize> Serialize for [u8; N] {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        Ok(writer.write_all(self)?)
+    }
+}
+
+impl<const N: usize> Deserialize for [u8; N] {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError>
+    where
+        Self: Sized,
+    {
+        let mut buf = [0; N];
+        reader.read_exact(&mut buf)?;
+
+        Ok(buf)
+    }
+}
+
+/// Boxed so large signatures don't bloat the packet structs.
+impl<const N: usize> Serialize for Box<[u8; N]> {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        Ok(writer.write_all(&**self)?)
+    }
+}
+
+impl<const N: usize> Deserialize for Box<[u8; N]> {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError>
+    where
+        Self: Sized,
+    {
+        Ok(Box::new(<[u8; N]>::deserialize(reader)?))
+    }
+}
+
+impl<T: Serialize> Serialize for Option<T> {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        match self {
+            Some(value) => {
+                true.serialize(writer)?;
+                value.serialize(writer)
+            }
+            None => false.serialize(writer),
+        }
+    }
+}
+
+impl<T: Deserialize> Deserialize for Option<T> {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError>
+    where
+        Self: Sized,
+    {
+        match bool::deserialize(reader)? {
+            true => Ok(Some(T::deserialize(reader)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+/// Array prefixed with its length as a VarInt.
+///
+/// `Vec<u8>` already encodes as a prefixed byte array, this covers every other
+/// element type.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrefixedArray<T>(pub Vec<T>);
+
+impl<T: Serialize> Serialize for PrefixedArray<T> {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        VarInt(self.0.len() as i32).write_sync(writer)?;
+        for item in &self.0 {
+            item.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Deserialize> Deserialize for PrefixedArray<T> {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError>
+    where
+        Self: Sized,
+    {
+        let len = VarInt::read_sync(reader)?;
+        if len.0 < 0 {
+            return Err(VarIntError::NegativeValue.into());
+        }
+
+        let mut items = Vec::new();
+        for _ in 0..len.0 {
+            items.push(T::deserialize(reader)?);
+        }
+
+        Ok(Self(items))
+    }
+}
+
+/// Remaining bytes of the packet without a length prefix. Must be the last field.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RestBuffer(pub Vec<u8>);
+
+impl Serialize for RestBuffer {
+    fn serialize<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), SerializationError> {
+        Ok(writer.write_all(&self.0)?)
+    }
+}
+
+impl Deserialize for RestBuffer {
+    fn deserialize<R: Read + Unpin>(reader: &mut R) -> Result<Self, SerializationError>
+    where
+        Self: Sized,
+    {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        Ok(Self(buf))
+    }
+}
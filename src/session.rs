@@ -0,0 +1,196 @@
+//! Mojang session server: `join` on the client, `hasJoined` on the server.
+//!
+//! The crate doesn't ship an HTTP client. Implement [`HttpTransport`] over
+//! the one you already use; the base URL can point at a mock or a
+//! third-party session server.
+//!
+//! ```no_run
+//! use std::io;
+//!
+//! use minecraft_protocol::session::{HttpResponse, HttpTransport, SessionClient, SessionError};
+//!
+//! /// Wraps the HTTP client of your choice
+//! struct Transport;
+//!
+//! impl HttpTransport for Transport {
+//!     async fn get(&self, _url: &str) -> io::Result<HttpResponse> {
+//!         // Send the request with your HTTP client here
+//!         Err(io::ErrorKind::Unsupported.into())
+//!     }
+//!
+//!     async fn post_json(&self, _url: &str, _body: &str) -> io::Result<HttpResponse> {
+//!         Err(io::ErrorKind::Unsupported.into())
+//!     }
+//! }
+//!
+//! // `hash` is `encryption::server_hash("", &shared_secret, &public_key)`
+//! async fn example(access_token: &str, profile_id: u128, hash: &str) -> Result<(), SessionError> {
+//!     let session = SessionClient::new(Transport);
+//!
+//!     // Client, before sending Encryption Response
+//!     session.join(access_token, profile_id, hash).await?;
+//!
+//!     // Server, after receiving it
+//!     let profile = session.has_joined("Steve", hash, None).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::{future::Future, io, net::IpAddr};
+
+use thiserror::Error;
+
+use crate::profile::{GameProfile, uuid_to_hex};
+
+pub const MOJANG_SESSION_SERVER: &str = "https://sessionserver.mojang.com";
+
+#[derive(Debug, Error)]
+pub enum SessionError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] io::Error),
+
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// `hasJoined` found no matching `join`.
+    #[error("Player has not joined the server")]
+    NotVerified,
+
+    #[error("Rate limited by the session server")]
+    RateLimited,
+
+    /// Usually an invalid access token or a banned account.
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Unexpected status {0}: {1}")]
+    UnexpectedStatus(u16, String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Minimal HTTP client used by [`SessionClient`]. Transport failures are
+/// reported as `io::Error`; any status code is a successful response.
+pub trait HttpTransport {
+    fn get(&self, url: &str) -> impl Future<Output = io::Result<HttpResponse>> + Send;
+
+    /// Sends `body` with `Content-Type: application/json`.
+    fn post_json(
+        &self,
+        url: &str,
+        body: &str,
+    ) -> impl Future<Output = io::Result<HttpResponse>> + Send;
+}
+
+pub struct SessionClient<T> {
+    transport: T,
+    base_url: String,
+}
+
+impl<T> SessionClient<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            base_url: MOJANG_SESSION_SERVER.to_string(),
+        }
+    }
+
+    /// Without the trailing slash, e.g. `http://127.0.0.1:8080`.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<T: HttpTransport> SessionClient<T> {
+    /// Client side: tells the session server that the selected profile is
+    /// joining the server identified by `server_hash`.
+    pub async fn join(
+        &self,
+        access_token: &str,
+        selected_profile: u128,
+        server_hash: &str,
+    ) -> Result<(), SessionError> {
+        let body = serde_json::json!({
+            "accessToken": access_token,
+            "selectedProfile": uuid_to_hex(selected_profile),
+            "serverId": server_hash,
+        });
+        let url = format!("{}/session/minecraft/join", self.base_url);
+
+        let response = self.transport.post_json(&url, &body.to_string()).await?;
+        match response.status {
+            200 | 204 => Ok(()),
+            _ => Err(error_for(response)),
+        }
+    }
+
+    /// Server side: checks that `username` joined with `server_hash` and
+    /// returns their signed profile. `ip` is only sent when given, for
+    /// servers that enable `prevent-proxy-connections`.
+    pub async fn has_joined(
+        &self,
+        username: &str,
+        server_hash: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<GameProfile, SessionError> {
+        let mut url = format!(
+            "{}/session/minecraft/hasJoined?username={}&serverId={}",
+            self.base_url,
+            encode_query(username),
+            encode_query(server_hash),
+        );
+        if let Some(ip) = ip {
+            url.push_str("&ip=");
+            url.push_str(&encode_query(&ip.to_string()));
+        }
+
+        let response = self.transport.get(&url).await?;
+        match response.status {
+            200 => Ok(serde_json::from_str(&response.body)?),
+            204 => Err(SessionError::NotVerified),
+            _ => Err(error_for(response)),
+        }
+    }
+}
+
+fn error_for(response: HttpResponse) -> SessionError {
+    match response.status {
+        429 => SessionError::RateLimited,
+        401 | 403 => {
+            // {"error": "ForbiddenOperationException", "errorMessage": "..."}
+            let message = serde_json::from_str::<serde_json::Value>(&response.body)
+                .ok()
+                .and_then(|json| json["errorMessage"].as_str().map(str::to_string))
+                .unwrap_or(response.body);
+            SessionError::Forbidden(message)
+        }
+        status => SessionError::UnexpectedStatus(status, response.body),
+    }
+}
+
+/// Percent-encodes everything except RFC 3986 unreserved characters.
+fn encode_query(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,182 @@
+use std::{
+    io,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+    time::timeout,
+};
+
+use super::StatusResponse;
+use crate::{
+    connection::{Connection, ConnectionError},
+    packets::{
+        PROTOCOL_VERSION,
+        handshake::serverbound::Handshake,
+        status::{
+            clientbound::{self, PongResponse},
+            serverbound::{PingRequest, StatusRequest},
+        },
+    },
+    varint::VarInt,
+};
+
+pub const DEFAULT_PORT: u16 = 25565;
+
+#[derive(Debug, Error)]
+pub enum StatusError {
+    #[error("Connection error: {0}")]
+    Connection(#[from] ConnectionError),
+
+    #[error("IO Error: {0}")]
+    IOError(#[from] io::Error),
+
+    #[error("Invalid status JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid server address {0}")]
+    InvalidAddress(String),
+
+    #[error("Timed out")]
+    Timeout,
+
+    #[error("Pong payload {actual} doesn't match ping payload {expected}")]
+    PongMismatch { expected: i64, actual: i64 },
+
+    #[error("Invalid legacy ping: {0}")]
+    InvalidLegacyPing(&'static str),
+}
+
+#[derive(Debug, Clone)]
+pub struct PingOptions {
+    /// Sent in the Handshake. Servers answer every version and report their own.
+    pub protocol_version: i32,
+    /// Limit for the whole exchange, connecting included.
+    pub timeout: Duration,
+    /// Send Ping Request after the status. Without it `latency` is `None`.
+    pub measure_latency: bool,
+}
+
+impl Default for PingOptions {
+    fn default() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION.0,
+            timeout: Duration::from_secs(5),
+            measure_latency: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingResponse {
+    pub status: StatusResponse,
+    /// Round trip of Ping Request / Pong Response.
+    pub latency: Option<Duration>,
+}
+
+/// Connects to `address` (`host`, `host:port`, `[v6]:port`; port defaults to
+/// 25565) and queries its status. SRV records are not resolved.
+pub async fn ping(address: &str, options: &PingOptions) -> Result<PingResponse, StatusError> {
+    let (host, port) = split_address(address)?;
+
+    timeout(options.timeout, async {
+        let stream = TcpStream::connect((host, port)).await?;
+        stream.set_nodelay(true)?;
+        exchange(stream, host, port, options).await
+    })
+    .await
+    .map_err(|_| StatusError::Timeout)?
+}
+
+/// [`ping`] over an already connected stream. `host` and `port` only go into
+/// the Handshake.
+pub async fn ping_stream<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    host: &str,
+    port: u16,
+    options: &PingOptions,
+) -> Result<PingResponse, StatusError> {
+    timeout(options.timeout, exchange(stream, host, port, options))
+        .await
+        .map_err(|_| StatusError::Timeout)?
+}
+
+async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    host: &str,
+    port: u16,
+    options: &PingOptions,
+) -> Result<PingResponse, StatusError> {
+    let mut connection = Connection::client(stream);
+
+    connection
+        .send(&Handshake {
+            protocol_version: VarInt(options.protocol_version),
+            server_address: host.to_string(),
+            server_port: port,
+            next_state: Handshake::STATUS,
+        })
+        .await?;
+    connection.send(&StatusRequest).await?;
+
+    let response: clientbound::StatusResponse = connection.recv().await?;
+    let status = StatusResponse::from_json(&response.json_response)?;
+
+    if !options.measure_latency {
+        return Ok(PingResponse {
+            status,
+            latency: None,
+        });
+    }
+
+    let payload = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as i64);
+    let start = Instant::now();
+    connection.send(&PingRequest { payload }).await?;
+    let pong: PongResponse = connection.recv().await?;
+    let latency = start.elapsed();
+
+    if pong.payload != payload {
+        return Err(StatusError::PongMismatch {
+            expected: payload,
+            actual: pong.payload,
+        });
+    }
+
+    Ok(PingResponse {
+        status,
+        latency: Some(latency),
+    })
+}
+
+pub(super) fn split_address(address: &str) -> Result<(&str, u16), StatusError> {
+    let invalid = || StatusError::InvalidAddress(address.to_string());
+
+    let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']').ok_or_else(invalid)?;
+        match rest {
+            "" => (host, None),
+            _ => (host, Some(rest.strip_prefix(':').ok_or_else(invalid)?)),
+        }
+    } else {
+        match address.split_once(':') {
+            // More than one colon is a bare IPv6 address
+            Some((host, port)) if !port.contains(':') => (host, Some(port)),
+            _ => (address, None),
+        }
+    };
+
+    if host.is_empty() {
+        return Err(invalid());
+    }
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| invalid())?,
+        None => DEFAULT_PORT,
+    };
+
+    Ok((host, port))
+}
@@ -0,0 +1,80 @@
+//! The `favicon` of the status JSON: a 64x64 PNG as a
+//! `data:image/png;base64,...` URI.
+//!
+//! Only the PNG signature and the IHDR chunk are checked, the image itself is
+//! not decoded.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use thiserror::Error;
+
+pub const DATA_URI_PREFIX: &str = "data:image/png;base64,";
+pub const SIZE: u32 = 64;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FaviconError {
+    #[error("Favicon is not a data:image/png;base64 URI")]
+    NotDataUri,
+
+    #[error("Invalid favicon base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    #[error("Favicon is not a PNG file")]
+    NotPng,
+
+    #[error("Favicon PNG is truncated")]
+    Truncated,
+
+    #[error("Favicon must be 64x64, got {width}x{height}")]
+    InvalidSize { width: u32, height: u32 },
+}
+
+/// Width and height from the IHDR chunk, which must come first.
+pub fn png_dimensions(png: &[u8]) -> Result<(u32, u32), FaviconError> {
+    if !png.starts_with(&PNG_SIGNATURE) {
+        return Err(if PNG_SIGNATURE.starts_with(png) {
+            FaviconError::Truncated
+        } else {
+            FaviconError::NotPng
+        });
+    }
+
+    // Chunk length, "IHDR", width, height
+    let Some(ihdr) = png.get(8..24) else {
+        return Err(FaviconError::Truncated);
+    };
+    if &ihdr[4..8] != b"IHDR" {
+        return Err(FaviconError::NotPng);
+    }
+    let width = u32::from_be_bytes(ihdr[8..12].try_into().unwrap());
+    let height = u32::from_be_bytes(ihdr[12..16].try_into().unwrap());
+    Ok((width, height))
+}
+
+/// Checks that `png` is a 64x64 PNG.
+pub fn validate(png: &[u8]) -> Result<(), FaviconError> {
+    match png_dimensions(png)? {
+        (SIZE, SIZE) => Ok(()),
+        (width, height) => Err(FaviconError::InvalidSize { width, height }),
+    }
+}
+
+/// Validates `png` and wraps it in a data URI.
+pub fn encode(png: &[u8]) -> Result<String, FaviconError> {
+    validate(png)?;
+    Ok(format!("{DATA_URI_PREFIX}{}", STANDARD.encode(png)))
+}
+
+/// Unwraps a data URI and validates the PNG. Line breaks in the base64 are
+/// skipped, some older servers send them.
+pub fn decode(uri: &str) -> Result<Vec<u8>, FaviconError> {
+    let data = uri
+        .strip_prefix(DATA_URI_PREFIX)
+        .ok_or(FaviconError::NotDataUri)?;
+    let data: String = data.chars().filter(|c| !matches!(c, '\r' | '\n')).collect();
+
+    let png = STANDARD.decode(data)?;
+    validate(&png)?;
+    Ok(png)
+}
@@ -0,0 +1,240 @@
+//! Pre-1.7 Server List Ping.
+//!
+//! Legacy clients open with `0xFE` instead of a VarInt length, and servers
+//! answer with a Kick packet (`0xFF`) holding a UTF-16BE string:
+//!
+//! | Client | Request | Response string |
+//! |---|---|---|
+//! | Beta 1.8 to 1.3 | `FE` | `motd§online§max` |
+//! | 1.4 and 1.5 | `FE 01` | `§1\0protocol\0version\0motd\0online\0max` |
+//! | 1.6 | `FE 01 FA` + `MC|PingHost` plugin message | same as 1.4 |
+
+use std::time::Duration;
+
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    time::timeout,
+};
+
+use super::client::{StatusError, split_address};
+
+const PING: u8 = 0xFE;
+const PING_PAYLOAD: u8 = 0x01;
+const PLUGIN_MESSAGE: u8 = 0xFA;
+const KICK: u8 = 0xFF;
+const PING_HOST_CHANNEL: &str = "MC|PingHost";
+
+/// How long to wait for the optional bytes after `0xFE` before assuming an
+/// older client. Real clients send the whole request at once.
+const FOLLOW_UP_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LegacyPing {
+    /// Beta 1.8 to 1.3: `FE`
+    Beta,
+    /// 1.4 and 1.5: `FE 01`
+    V1_4,
+    /// 1.6: `FE 01 FA` followed by `MC|PingHost`
+    V1_6 {
+        protocol_version: u8,
+        host: String,
+        port: u16,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyStatus {
+    /// Protocol number and version name, missing in Beta responses.
+    pub version: Option<(i32, String)>,
+    pub motd: String,
+    pub online: i32,
+    pub max_players: i32,
+}
+
+impl LegacyStatus {
+    /// The Kick string for a client that sent `ping`.
+    pub fn to_kick_string(&self, ping: &LegacyPing) -> String {
+        match ping {
+            // Beta clients split on §, so it can't appear in the MOTD
+            LegacyPing::Beta => format!(
+                "{}§{}§{}",
+                self.motd.replace('§', ""),
+                self.online,
+                self.max_players
+            ),
+            _ => {
+                let (protocol, name) = self.version.clone().unwrap_or((-1, String::new()));
+                format!(
+                    "§1\0{protocol}\0{name}\0{}\0{}\0{}",
+                    self.motd, self.online, self.max_players
+                )
+            }
+        }
+    }
+
+    pub fn from_kick_string(kick: &str) -> Result<Self, StatusError> {
+        let invalid = || StatusError::InvalidLegacyPing("malformed status string");
+
+        if let Some(rest) = kick.strip_prefix("§1\0") {
+            let fields: Vec<&str> = rest.split('\0').collect();
+            let [protocol, name, motd, online, max] = fields[..] else {
+                return Err(invalid());
+            };
+            return Ok(Self {
+                version: Some((protocol.parse().map_err(|_| invalid())?, name.to_string())),
+                motd: motd.to_string(),
+                online: online.parse().map_err(|_| invalid())?,
+                max_players: max.parse().map_err(|_| invalid())?,
+            });
+        }
+
+        let mut fields = kick.rsplitn(3, '§');
+        let (Some(max), Some(online), Some(motd)) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid());
+        };
+        Ok(Self {
+            version: None,
+            motd: motd.to_string(),
+            online: online.parse().map_err(|_| invalid())?,
+            max_players: max.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Whether the next byte on `stream` starts a legacy ping. Nothing is
+/// consumed, so a modern client can still be read normally.
+pub async fn is_legacy_ping(stream: &TcpStream) -> Result<bool, StatusError> {
+    let mut first = [0];
+    Ok(stream.peek(&mut first).await? == 1 && first[0] == PING)
+}
+
+/// Server side: reads a legacy ping request, `0xFE` included.
+pub async fn read_legacy_ping<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<LegacyPing, StatusError> {
+    if reader.read_u8().await? != PING {
+        return Err(StatusError::InvalidLegacyPing("not a legacy ping"));
+    }
+
+    match next_byte(reader).await? {
+        None => return Ok(LegacyPing::Beta),
+        Some(PING_PAYLOAD) => {}
+        Some(_) => return Err(StatusError::InvalidLegacyPing("unknown ping payload")),
+    }
+
+    match next_byte(reader).await? {
+        None => return Ok(LegacyPing::V1_4),
+        Some(PLUGIN_MESSAGE) => {}
+        Some(_) => return Err(StatusError::InvalidLegacyPing("expected MC|PingHost")),
+    }
+
+    if read_utf16(reader).await? != PING_HOST_CHANNEL {
+        return Err(StatusError::InvalidLegacyPing("expected MC|PingHost"));
+    }
+    let _data_length = reader.read_u16().await?;
+    let protocol_version = reader.read_u8().await?;
+    let host = read_utf16(reader).await?;
+    let port = reader.read_i32().await?;
+
+    Ok(LegacyPing::V1_6 {
+        protocol_version,
+        host,
+        port: u16::try_from(port).map_err(|_| StatusError::InvalidLegacyPing("port"))?,
+    })
+}
+
+/// Server side: answers `ping` and flushes. The client closes the
+/// connection afterwards.
+pub async fn write_legacy_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    ping: &LegacyPing,
+    status: &LegacyStatus,
+) -> Result<(), StatusError> {
+    let mut buf = vec![KICK];
+    write_utf16(&mut buf, &status.to_kick_string(ping));
+    writer.write_all(&buf).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Client side: sends `ping` over `stream` and parses the Kick response.
+pub async fn legacy_ping_stream<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    ping: &LegacyPing,
+) -> Result<LegacyStatus, StatusError> {
+    let mut request = vec![PING];
+    if let LegacyPing::V1_4 | LegacyPing::V1_6 { .. } = ping {
+        request.push(PING_PAYLOAD);
+    }
+    if let LegacyPing::V1_6 {
+        protocol_version,
+        host,
+        port,
+    } = ping
+    {
+        request.push(PLUGIN_MESSAGE);
+        write_utf16(&mut request, PING_HOST_CHANNEL);
+        let host_units = host.encode_utf16().count() as u16;
+        request.extend_from_slice(&(7 + 2 * host_units).to_be_bytes());
+        request.push(*protocol_version);
+        write_utf16(&mut request, host);
+        request.extend_from_slice(&(*port as i32).to_be_bytes());
+    }
+    stream.write_all(&request).await?;
+    stream.flush().await?;
+
+    if stream.read_u8().await? != KICK {
+        return Err(StatusError::InvalidLegacyPing("expected a Kick packet"));
+    }
+    LegacyStatus::from_kick_string(&read_utf16(stream).await?)
+}
+
+/// Client side: connects to `address` and sends `ping`, within `limit`.
+/// For [`LegacyPing::V1_6`] the host and port to send are taken from `ping`.
+pub async fn legacy_ping(
+    address: &str,
+    ping: &LegacyPing,
+    limit: Duration,
+) -> Result<LegacyStatus, StatusError> {
+    let (host, port) = split_address(address)?;
+
+    timeout(limit, async {
+        let mut stream = TcpStream::connect((host, port)).await?;
+        legacy_ping_stream(&mut stream, ping).await
+    })
+    .await
+    .map_err(|_| StatusError::Timeout)?
+}
+
+/// `None` if nothing more arrives in time or the client stopped sending.
+async fn next_byte<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<u8>, StatusError> {
+    let mut byte = [0];
+    match timeout(FOLLOW_UP_TIMEOUT, reader.read(&mut byte)).await {
+        Ok(Ok(1)) => Ok(Some(byte[0])),
+        Ok(Ok(_)) | Err(_) => Ok(None),
+        Ok(Err(e)) => Err(e.into()),
+    }
+}
+
+/// Length in UTF-16 code units as a big-endian short, then UTF-16BE.
+async fn read_utf16<R: AsyncRead + Unpin>(reader: &mut R) -> Result<String, StatusError> {
+    let len = reader.read_u16().await? as usize;
+    let mut bytes = vec![0; len * 2];
+    reader.read_exact(&mut bytes).await?;
+
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(|_| StatusError::InvalidLegacyPing("invalid UTF-16"))
+}
+
+fn write_utf16(buf: &mut Vec<u8>, text: &str) {
+    let units: Vec<u16> = text.encode_utf16().collect();
+    buf.extend_from_slice(&(units.len() as u16).to_be_bytes());
+    for unit in units {
+        buf.extend_from_slice(&unit.to_be_bytes());
+    }
+}
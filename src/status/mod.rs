@@ -0,0 +1,112 @@
+//! Server List Ping: the JSON status a server shows in the multiplayer menu.
+//!
+//! With the `tokio` feature, [`ping`] queries a server end to end and
+//! [`legacy`] covers the pre-1.7 ping. [`StatusResponder`] is the server side.
+
+#[cfg(feature = "tokio")]
+mod client;
+pub mod favicon;
+#[cfg(feature = "tokio")]
+pub mod legacy;
+#[cfg(feature = "tokio")]
+mod server;
+
+pub use favicon::FaviconError;
+
+#[cfg(feature = "tokio")]
+pub use client::{DEFAULT_PORT, PingOptions, PingResponse, StatusError, ping, ping_stream};
+#[cfg(feature = "tokio")]
+pub use server::{Served, StatusResponder};
+
+/// Parsed `json_response` of the Status Response packet.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusResponse {
+    pub version: StatusVersion,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub players: Option<StatusPlayers>,
+    /// Text component: a plain string or a JSON object.
+    #[serde(default)]
+    pub description: serde_json::Value,
+    /// `data:image/png;base64,...` of a 64x64 PNG, see [`favicon`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<String>,
+    #[serde(default)]
+    pub enforces_secure_chat: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StatusVersion {
+    pub name: String,
+    pub protocol: i32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StatusPlayers {
+    pub max: i32,
+    pub online: i32,
+    /// A few online players. Servers often put arbitrary lines of text here.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sample: Vec<PlayerSample>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerSample {
+    pub name: String,
+    /// Dashed UUID
+    pub id: String,
+}
+
+impl StatusResponse {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("status response is always valid JSON")
+    }
+
+    /// The description without formatting: `text` of the component and its
+    /// `extra` children, in order.
+    pub fn description_text(&self) -> String {
+        plain_text(&self.description)
+    }
+
+    /// Sets `favicon` to a data URI of `png`, which must be a 64x64 PNG.
+    pub fn set_favicon_png(&mut self, png: &[u8]) -> Result<(), FaviconError> {
+        self.favicon = Some(favicon::encode(png)?);
+        Ok(())
+    }
+
+    /// The PNG bytes of `favicon`, `None` if the server sent none.
+    pub fn favicon_png(&self) -> Result<Option<Vec<u8>>, FaviconError> {
+        self.favicon.as_deref().map(favicon::decode).transpose()
+    }
+}
+
+/// A text component without formatting.
+pub(crate) fn plain_text(component: &serde_json::Value) -> String {
+    let mut text = String::new();
+    flatten_text(component, &mut text);
+    text
+}
+
+fn flatten_text(component: &serde_json::Value, out: &mut String) {
+    match component {
+        serde_json::Value::String(text) => out.push_str(text),
+        serde_json::Value::Array(children) => {
+            for child in children {
+                flatten_text(child, out);
+            }
+        }
+        serde_json::Value::Object(object) => {
+            if let Some(text) = object.get("text") {
+                flatten_text(text, out);
+            }
+            if let Some(extra) = object.get("extra") {
+                flatten_text(extra, out);
+            }
+        }
+        _ => {}
+    }
+}
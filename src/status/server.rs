@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+    time::timeout,
+};
+
+use super::{
+    StatusResponse,
+    client::StatusError,
+    legacy::{self, LegacyStatus},
+};
+use crate::{
+    connection::{Connection, ConnectionError},
+    packets::{
+        handshake::serverbound::Handshake,
+        login::clientbound::Disconnect,
+        status::{
+            clientbound::{self, PongResponse},
+            serverbound::{PingRequest, StatusRequest},
+        },
+    },
+};
+
+/// Answers Server List Pings with a fixed [`StatusResponse`], without running
+/// a game.
+///
+/// ```no_run
+/// # async fn example(status: minecraft_protocol::status::StatusResponse) -> std::io::Result<()> {
+/// use minecraft_protocol::status::StatusResponder;
+///
+/// let responder = StatusResponder::new(status).with_login_kick("Down for maintenance");
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:25565").await?;
+/// loop {
+///     let (socket, _) = listener.accept().await?;
+///     let responder = responder.clone();
+///     tokio::spawn(async move { responder.serve_tcp(socket).await });
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatusResponder {
+    status: StatusResponse,
+    login_kick: Option<serde_json::Value>,
+    timeout: Duration,
+}
+
+/// What the client asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Served {
+    /// Status sent. `pinged` is false if the client closed before Ping Request.
+    Status { handshake: Handshake, pinged: bool },
+    /// A login attempt, answered with Disconnect if `kicked`.
+    Login { handshake: Handshake, kicked: bool },
+    /// A pre-1.7 client, see [`legacy`].
+    Legacy(legacy::LegacyPing),
+}
+
+impl StatusResponder {
+    pub fn new(status: StatusResponse) -> Self {
+        Self {
+            status,
+            login_kick: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Disconnects login attempts with `reason`, a text component. Without it
+    /// they are closed silently.
+    pub fn with_login_kick(mut self, reason: impl Into<serde_json::Value>) -> Self {
+        self.login_kick = Some(reason.into());
+        self
+    }
+
+    /// Limit for one client, 10 seconds by default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn status(&self) -> &StatusResponse {
+        &self.status
+    }
+
+    pub fn status_mut(&mut self) -> &mut StatusResponse {
+        &mut self.status
+    }
+
+    /// Serves one client on an accepted socket, legacy pings included.
+    pub async fn serve_tcp(&self, mut stream: TcpStream) -> Result<Served, StatusError> {
+        timeout(self.timeout, async {
+            if legacy::is_legacy_ping(&stream).await? {
+                let ping = legacy::read_legacy_ping(&mut stream).await?;
+                legacy::write_legacy_response(&mut stream, &ping, &self.legacy_status()).await?;
+                return Ok(Served::Legacy(ping));
+            }
+            self.exchange(stream).await
+        })
+        .await
+        .map_err(|_| StatusError::Timeout)?
+    }
+
+    /// Serves one client that speaks the modern protocol.
+    pub async fn serve<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        stream: S,
+    ) -> Result<Served, StatusError> {
+        timeout(self.timeout, self.exchange(stream))
+            .await
+            .map_err(|_| StatusError::Timeout)?
+    }
+
+    /// The status as pre-1.7 clients see it.
+    pub fn legacy_status(&self) -> LegacyStatus {
+        let players = self.status.players.as_ref();
+        LegacyStatus {
+            version: Some((
+                self.status.version.protocol,
+                self.status.version.name.clone(),
+            )),
+            motd: self.status.description_text(),
+            online: players.map_or(0, |players| players.online),
+            max_players: players.map_or(0, |players| players.max),
+        }
+    }
+
+    async fn exchange<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        stream: S,
+    ) -> Result<Served, StatusError> {
+        let mut connection = Connection::server(stream);
+        let handshake: Handshake = connection.recv().await?;
+
+        if handshake.next_state != Handshake::STATUS {
+            let Some(reason) = &self.login_kick else {
+                return Ok(Served::Login {
+                    handshake,
+                    kicked: false,
+                });
+            };
+            // Wait for Login Start: closing with it unread may reset the
+            // connection before the client sees Disconnect.
+            connection.recv_raw().await?;
+            connection
+                .send(&Disconnect {
+                    reason: reason.to_string(),
+                })
+                .await?;
+            return Ok(Served::Login {
+                handshake,
+                kicked: true,
+            });
+        }
+
+        connection.recv::<StatusRequest>().await?;
+        connection
+            .send(&clientbound::StatusResponse {
+                json_response: self.status.to_json(),
+            })
+            .await?;
+
+        let pinged = match connection.recv::<PingRequest>().await {
+            Ok(ping) => {
+                connection
+                    .send(&PongResponse {
+                        payload: ping.payload,
+                    })
+                    .await?;
+                true
+            }
+            Err(ConnectionError::Closed) => false,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Served::Status { handshake, pinged })
+    }
+}
@@ -1,7 +1,6 @@
 use std::io::{self, Read, Write};
 
 use thiserror::Error;
-use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 #[derive(Debug, Error)]
 pub enum VarIntError {
@@ -16,95 +15,224 @@ pub enum VarIntError {
 const SEGMENT_BITS: i32 = 0x7F;
 const CONTINUE_BIT: i32 = 0x80;
 
+/// Adds one byte to a VarInt/VarLong being read. Returns `true` after the last byte.
+///
+/// Shared by the blocking, tokio and futures-io readers.
+fn push_byte(
+    value: &mut i64,
+    position: &mut i32,
+    byte: u8,
+    max_position: i32,
+) -> Result<bool, VarIntError> {
+    *value |= (byte as i64 & SEGMENT_BITS as i64) << *position;
+
+    if (byte as i32 & CONTINUE_BIT) == 0 {
+        return Ok(true);
+    }
+
+    *position += 7;
+    if *position >= max_position {
+        return Err(VarIntError::Position);
+    }
+
+    Ok(false)
+}
+
+fn read_byte_sync<R: Read + Unpin>(reader: &mut R, what: &str) -> Result<u8, VarIntError> {
+    let mut buf = [0; 1];
+    if reader.read(&mut buf)? == 0 {
+        return Err(VarIntError::IOError(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("Incomplete {what}"),
+        )));
+    }
+    Ok(buf[0])
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VarInt(pub i32);
 
 impl VarInt {
-    pub async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> Result<VarInt, VarIntError> {
-        let mut value: i32 = 0;
-        let mut position: i32 = 0;
+    #[cfg(feature = "tokio")]
+    pub async fn read<R: tokio::io::AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<VarInt, VarIntError> {
+        use tokio::io::AsyncReadExt;
 
-        loop {
-            let current_byte = reader.read_u8().await? as i32;
+        let (mut value, mut position) = (0, 0);
+        while !push_byte(&mut value, &mut position, reader.read_u8().await?, 32)? {}
 
-            value |= (current_byte & SEGMENT_BITS) << position;
+        Ok(VarInt(value as i32))
+    }
 
-            if (current_byte & CONTINUE_BIT) == 0 {
-                break;
-            }
-            position += 7;
+    /// [`read`](Self::read) for `futures::io::AsyncRead`.
+    #[cfg(feature = "futures-io")]
+    pub async fn read_futures<R: futures_io::AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<VarInt, VarIntError> {
+        use futures_util::AsyncReadExt;
 
-            if position >= 32 {
-                return Err(VarIntError::Position);
+        let (mut value, mut position) = (0, 0);
+        loop {
+            let mut buf = [0; 1];
+            reader.read_exact(&mut buf).await?;
+            if push_byte(&mut value, &mut position, buf[0], 32)? {
+                break;
             }
         }
 
-        Ok(VarInt(value))
+        Ok(VarInt(value as i32))
     }
 
     pub fn read_sync<R: Read + Unpin>(reader: &mut R) -> Result<Self, VarIntError> {
-        let mut value: i32 = 0;
-        let mut position: i32 = 0;
+        let (mut value, mut position) = (0, 0);
+        while !push_byte(
+            &mut value,
+            &mut position,
+            read_byte_sync(reader, "VarInt")?,
+            32,
+        )? {}
+
+        Ok(VarInt(value as i32))
+    }
 
-        loop {
-            let mut buf = [0; 1];
-            if reader.read(&mut buf)? == 0 {
-                return Err(VarIntError::IOError(io::Error::new(
-                    io::ErrorKind::UnexpectedEof,
-                    "Incomplete VarInt",
-                )));
-            }
+    #[cfg(feature = "tokio")]
+    pub async fn write<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), VarIntError> {
+        use tokio::io::AsyncWriteExt;
 
-            let current_byte = buf[0] as i32;
-            value |= (current_byte & SEGMENT_BITS) << position;
+        writer.write_all(&self.to_bytes()).await?;
+        Ok(())
+    }
 
-            if (current_byte & CONTINUE_BIT) == 0 {
-                break;
-            }
+    /// [`write`](Self::write) for `futures::io::AsyncWrite`.
+    #[cfg(feature = "futures-io")]
+    pub async fn write_futures<W: futures_io::AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), VarIntError> {
+        use futures_util::AsyncWriteExt;
 
-            position += 7;
-            if position >= 32 {
-                return Err(VarIntError::Position);
-            }
-        }
+        writer.write_all(&self.to_bytes()).await?;
+        Ok(())
+    }
 
-        Ok(VarInt(value))
+    pub fn write_sync<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), VarIntError> {
+        writer.write_all(&self.to_bytes())?;
+        Ok(())
     }
 
-    pub async fn write<W: AsyncWrite + Unpin>(
-        self: &Self,
-        writer: &mut W,
-    ) -> Result<(), VarIntError> {
+    /// Encoded form, 1 to 5 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(5);
         let mut value = self.0;
         loop {
             if (value & !SEGMENT_BITS) == 0 {
-                writer.write_u8(value as u8).await?;
+                bytes.push(value as u8);
                 break;
             }
 
-            writer
-                .write_u8(((value & SEGMENT_BITS) | CONTINUE_BIT) as u8)
-                .await?;
+            bytes.push(((value & SEGMENT_BITS) | CONTINUE_BIT) as u8);
 
             value = ((value as u32) >> 7) as i32;
         }
 
+        bytes
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VarLong(pub i64);
+
+impl VarLong {
+    #[cfg(feature = "tokio")]
+    pub async fn read<R: tokio::io::AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<VarLong, VarIntError> {
+        use tokio::io::AsyncReadExt;
+
+        let (mut value, mut position) = (0, 0);
+        while !push_byte(&mut value, &mut position, reader.read_u8().await?, 64)? {}
+
+        Ok(VarLong(value))
+    }
+
+    /// [`read`](Self::read) for `futures::io::AsyncRead`.
+    #[cfg(feature = "futures-io")]
+    pub async fn read_futures<R: futures_io::AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<VarLong, VarIntError> {
+        use futures_util::AsyncReadExt;
+
+        let (mut value, mut position) = (0, 0);
+        loop {
+            let mut buf = [0; 1];
+            reader.read_exact(&mut buf).await?;
+            if push_byte(&mut value, &mut position, buf[0], 64)? {
+                break;
+            }
+        }
+
+        Ok(VarLong(value))
+    }
+
+    pub fn read_sync<R: Read + Unpin>(reader: &mut R) -> Result<Self, VarIntError> {
+        let (mut value, mut position) = (0, 0);
+        while !push_byte(
+            &mut value,
+            &mut position,
+            read_byte_sync(reader, "VarLong")?,
+            64,
+        )? {}
+
+        Ok(VarLong(value))
+    }
+
+    #[cfg(feature = "tokio")]
+    pub async fn write<W: tokio::io::AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), VarIntError> {
+        use tokio::io::AsyncWriteExt;
+
+        writer.write_all(&self.to_bytes()).await?;
         Ok(())
     }
 
-    pub fn write_sync<W: Write + Unpin>(self: &Self, writer: &mut W) -> Result<(), VarIntError> {
+    /// [`write`](Self::write) for `futures::io::AsyncWrite`.
+    #[cfg(feature = "futures-io")]
+    pub async fn write_futures<W: futures_io::AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), VarIntError> {
+        use futures_util::AsyncWriteExt;
+
+        writer.write_all(&self.to_bytes()).await?;
+        Ok(())
+    }
+
+    pub fn write_sync<W: Write + Unpin>(&self, writer: &mut W) -> Result<(), VarIntError> {
+        writer.write_all(&self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Encoded form, 1 to 10 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(10);
         let mut value = self.0;
         loop {
-            if (value & !SEGMENT_BITS) == 0 {
-                writer.write(&[value as u8])?;
+            if (value & !(SEGMENT_BITS as i64)) == 0 {
+                bytes.push(value as u8);
                 break;
             }
 
-            writer.write(&[((value & SEGMENT_BITS) | CONTINUE_BIT) as u8])?;
+            bytes.push(((value & SEGMENT_BITS as i64) | CONTINUE_BIT as i64) as u8);
 
-            value = ((value as u32) >> 7) as i32;
+            value = ((value as u64) >> 7) as i64;
         }
 
-        Ok(())
+        bytes
     }
 }
@@ -0,0 +1,276 @@
+use std::fmt;
+
+/// Bit set in the protocol number of development snapshots since 1.16.4-pre1.
+pub const SNAPSHOT_BIT: i32 = 0x4000_0000;
+
+/// Java Edition protocol version number as sent in the Handshake packet.
+///
+/// Ordering follows the raw number, so `ProtocolVersion::V1_20_5 <= v` reads
+/// as "1.20.5 or newer". Snapshots have [`SNAPSHOT_BIT`] set and therefore
+/// compare greater than every release.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtocolVersion(pub i32);
+
+impl ProtocolVersion {
+    pub const V1_7_2: Self = Self(4);
+    pub const V1_7_4: Self = Self(4);
+    pub const V1_7_5: Self = Self(4);
+    pub const V1_7_6: Self = Self(5);
+    pub const V1_7_7: Self = Self(5);
+    pub const V1_7_8: Self = Self(5);
+    pub const V1_7_9: Self = Self(5);
+    pub const V1_7_10: Self = Self(5);
+    pub const V1_8: Self = Self(47);
+    pub const V1_8_1: Self = Self(47);
+    pub const V1_8_2: Self = Self(47);
+    pub const V1_8_3: Self = Self(47);
+    pub const V1_8_4: Self = Self(47);
+    pub const V1_8_5: Self = Self(47);
+    pub const V1_8_6: Self = Self(47);
+    pub const V1_8_7: Self = Self(47);
+    pub const V1_8_8: Self = Self(47);
+    pub const V1_8_9: Self = Self(47);
+    pub const V1_9: Self = Self(107);
+    pub const V1_9_1: Self = Self(108);
+    pub const V1_9_2: Self = Self(109);
+    pub const V1_9_3: Self = Self(110);
+    pub const V1_9_4: Self = Self(110);
+    pub const V1_10: Self = Self(210);
+    pub const V1_10_1: Self = Self(210);
+    pub const V1_10_2: Self = Self(210);
+    pub const V1_11: Self = Self(315);
+    pub const V1_11_1: Self = Self(316);
+    pub const V1_11_2: Self = Self(316);
+    pub const V1_12: Self = Self(335);
+    pub const V1_12_1: Self = Self(338);
+    pub const V1_12_2: Self = Self(340);
+    pub const V1_13: Self = Self(393);
+    pub const V1_13_1: Self = Self(401);
+    pub const V1_13_2: Self = Self(404);
+    pub const V1_14: Self = Self(477);
+    pub const V1_14_1: Self = Self(480);
+    pub const V1_14_2: Self = Self(485);
+    pub const V1_14_3: Self = Self(490);
+    pub const V1_14_4: Self = Self(498);
+    pub const V1_15: Self = Self(573);
+    pub const V1_15_1: Self = Self(575);
+    pub const V1_15_2: Self = Self(578);
+    pub const V1_16: Self = Self(735);
+    pub const V1_16_1: Self = Self(736);
+    pub const V1_16_2: Self = Self(751);
+    pub const V1_16_3: Self = Self(753);
+    pub const V1_16_4: Self = Self(754);
+    pub const V1_16_5: Self = Self(754);
+    pub const V1_17: Self = Self(755);
+    pub const V1_17_1: Self = Self(756);
+    pub const V1_18: Self = Self(757);
+    pub const V1_18_1: Self = Self(757);
+    pub const V1_18_2: Self = Self(758);
+    pub const V1_19: Self = Self(759);
+    pub const V1_19_1: Self = Self(760);
+    pub const V1_19_2: Self = Self(760);
+    pub const V1_19_3: Self = Self(761);
+    pub const V1_19_4: Self = Self(762);
+    pub const V1_20: Self = Self(763);
+    pub const V1_20_1: Self = Self(763);
+    pub const V1_20_2: Self = Self(764);
+    pub const V1_20_3: Self = Self(765);
+    pub const V1_20_4: Self = Self(765);
+    pub const V1_20_5: Self = Self(766);
+    pub const V1_20_6: Self = Self(766);
+    pub const V1_21: Self = Self(767);
+    pub const V1_21_1: Self = Self(767);
+    pub const V1_21_2: Self = Self(768);
+    pub const V1_21_3: Self = Self(768);
+    pub const V1_21_4: Self = Self(769);
+    pub const V1_21_5: Self = Self(770);
+    pub const V1_21_6: Self = Self(771);
+    pub const V1_21_7: Self = Self(772);
+    pub const V1_21_8: Self = Self(772);
+    pub const V1_21_9: Self = Self(773);
+    pub const V1_21_10: Self = Self(773);
+
+    /// Newest release in [`RELEASES`].
+    pub const LATEST: Self = Self::V1_21_10;
+
+    /// Protocol number of the `n`-th development snapshot (high-bit form).
+    pub const fn snapshot(n: i32) -> Self {
+        Self(SNAPSHOT_BIT | n)
+    }
+
+    pub const fn is_snapshot(&self) -> bool {
+        self.0 & SNAPSHOT_BIT != 0
+    }
+
+    /// Returns the snapshot number without [`SNAPSHOT_BIT`], or `None` for releases.
+    pub const fn snapshot_number(&self) -> Option<i32> {
+        if self.is_snapshot() {
+            Some(self.0 & !SNAPSHOT_BIT)
+        } else {
+            None
+        }
+    }
+
+    /// Looks up a release by name, e.g. `"1.21.1"`.
+    pub fn from_release_name(name: &str) -> Option<Self> {
+        Release::by_name(name).map(|r| r.protocol)
+    }
+
+    /// Looks up the release that uses the given world data version.
+    pub fn from_data_version(data_version: i32) -> Option<Self> {
+        Release::by_data_version(data_version).map(|r| r.protocol)
+    }
+
+    /// All releases that speak this protocol version, oldest first.
+    pub fn releases(&self) -> impl Iterator<Item = &'static Release> {
+        let protocol = *self;
+        RELEASES.iter().filter(move |r| r.protocol == protocol)
+    }
+
+    /// `true` if at least one known release uses this protocol version.
+    pub fn is_known(&self) -> bool {
+        self.releases().next().is_some()
+    }
+}
+
+impl From<i32> for ProtocolVersion {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ProtocolVersion> for i32 {
+    fn from(value: ProtocolVersion) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(n) = self.snapshot_number() {
+            return write!(f, "snapshot {n} ({})", self.0);
+        }
+
+        let mut releases = self.releases();
+        match (releases.next(), releases.last()) {
+            (Some(first), Some(last)) => write!(f, "{}-{} ({})", first.name, last.name, self.0),
+            (Some(first), None) => write!(f, "{} ({})", first.name, self.0),
+            _ => write!(f, "unknown ({})", self.0),
+        }
+    }
+}
+
+/// A Java Edition release with its protocol number and world data version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Release {
+    pub name: &'static str,
+    pub protocol: ProtocolVersion,
+    /// Data version stored in `level.dat`, introduced in 1.9.
+    pub data_version: Option<i32>,
+}
+
+impl Release {
+    const fn new(name: &'static str, protocol: i32, data_version: Option<i32>) -> Self {
+        Self {
+            name,
+            protocol: ProtocolVersion(protocol),
+            data_version,
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<&'static Release> {
+        RELEASES.iter().find(|r| r.name == name)
+    }
+
+    pub fn by_data_version(data_version: i32) -> Option<&'static Release> {
+        RELEASES
+            .iter()
+            .find(|r| r.data_version == Some(data_version))
+    }
+
+    /// Newest release that speaks `protocol`.
+    pub fn latest_for(protocol: ProtocolVersion) -> Option<&'static Release> {
+        protocol.releases().last()
+    }
+}
+
+/// Known Java Edition releases, oldest first.
+pub const RELEASES: &[Release] = &[
+    Release::new("1.7.2", 4, None),
+    Release::new("1.7.4", 4, None),
+    Release::new("1.7.5", 4, None),
+    Release::new("1.7.6", 5, None),
+    Release::new("1.7.7", 5, None),
+    Release::new("1.7.8", 5, None),
+    Release::new("1.7.9", 5, None),
+    Release::new("1.7.10", 5, None),
+    Release::new("1.8", 47, None),
+    Release::new("1.8.1", 47, None),
+    Release::new("1.8.2", 47, None),
+    Release::new("1.8.3", 47, None),
+    Release::new("1.8.4", 47, None),
+    Release::new("1.8.5", 47, None),
+    Release::new("1.8.6", 47, None),
+    Release::new("1.8.7", 47, None),
+    Release::new("1.8.8", 47, None),
+    Release::new("1.8.9", 47, None),
+    Release::new("1.9", 107, Some(169)),
+    Release::new("1.9.1", 108, Some(175)),
+    Release::new("1.9.2", 109, Some(176)),
+    Release::new("1.9.3", 110, Some(183)),
+    Release::new("1.9.4", 110, Some(184)),
+    Release::new("1.10", 210, Some(510)),
+    Release::new("1.10.1", 210, Some(511)),
+    Release::new("1.10.2", 210, Some(512)),
+    Release::new("1.11", 315, Some(819)),
+    Release::new("1.11.1", 316, Some(921)),
+    Release::new("1.11.2", 316, Some(922)),
+    Release::new("1.12", 335, Some(1139)),
+    Release::new("1.12.1", 338, Some(1241)),
+    Release::new("1.12.2", 340, Some(1343)),
+    Release::new("1.13", 393, Some(1519)),
+    Release::new("1.13.1", 401, Some(1628)),
+    Release::new("1.13.2", 404, Some(1631)),
+    Release::new("1.14", 477, Some(1952)),
+    Release::new("1.14.1", 480, Some(1957)),
+    Release::new("1.14.2", 485, Some(1963)),
+    Release::new("1.14.3", 490, Some(1968)),
+    Release::new("1.14.4", 498, Some(1976)),
+    Release::new("1.15", 573, Some(2225)),
+    Release::new("1.15.1", 575, Some(2227)),
+    Release::new("1.15.2", 578, Some(2230)),
+    Release::new("1.16", 735, Some(2566)),
+    Release::new("1.16.1", 736, Some(2567)),
+    Release::new("1.16.2", 751, Some(2578)),
+    Release::new("1.16.3", 753, Some(2580)),
+    Release::new("1.16.4", 754, Some(2584)),
+    Release::new("1.16.5", 754, Some(2586)),
+    Release::new("1.17", 755, Some(2724)),
+    Release::new("1.17.1", 756, Some(2730)),
+    Release::new("1.18", 757, Some(2860)),
+    Release::new("1.18.1", 757, Some(2865)),
+    Release::new("1.18.2", 758, Some(2975)),
+    Release::new("1.19", 759, Some(3105)),
+    Release::new("1.19.1", 760, Some(3117)),
+    Release::new("1.19.2", 760, Some(3120)),
+    Release::new("1.19.3", 761, Some(3218)),
+    Release::new("1.19.4", 762, Some(3337)),
+    Release::new("1.20", 763, Some(3463)),
+    Release::new("1.20.1", 763, Some(3465)),
+    Release::new("1.20.2", 764, Some(3578)),
+    Release::new("1.20.3", 765, Some(3698)),
+    Release::new("1.20.4", 765, Some(3700)),
+    Release::new("1.20.5", 766, Some(3837)),
+    Release::new("1.20.6", 766, Some(3839)),
+    Release::new("1.21", 767, Some(3953)),
+    Release::new("1.21.1", 767, Some(3955)),
+    Release::new("1.21.2", 768, Some(4080)),
+    Release::new("1.21.3", 768, Some(4082)),
+    Release::new("1.21.4", 769, Some(4189)),
+    Release::new("1.21.5", 770, Some(4325)),
+    Release::new("1.21.6", 771, Some(4435)),
+    Release::new("1.21.7", 772, Some(4438)),
+    Release::new("1.21.8", 772, Some(4440)),
+    Release::new("1.21.9", 773, Some(4554)),
+    Release::new("1.21.10", 773, Some(4556)),
+];
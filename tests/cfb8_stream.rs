@@ -0,0 +1,278 @@
+#![cfg(any(feature = "openssl", feature = "rustcrypto"))]
+
+use std::io::{self, Write};
+
+use minecraft_protocol::{
+    cfb8_stream::CFB8WriteHalf,
+    cipher::{Cfb8Cipher, DefaultCipher, Direction},
+};
+
+const KEY: [u8; 16] = *b"0123456789abcdef";
+
+/// Takes at most 3 bytes per call and refuses every other call.
+#[derive(Default)]
+struct Trickle {
+    data: Vec<u8>,
+    calls: usize,
+}
+
+impl Trickle {
+    fn accept(&mut self, buf: &[u8]) -> Option<usize> {
+        self.calls += 1;
+        if self.calls.is_multiple_of(2) {
+            return None;
+        }
+        let count = buf.len().min(3);
+        self.data.extend_from_slice(&buf[..count]);
+        Some(count)
+    }
+
+    #[cfg(any(feature = "tokio", feature = "futures-io"))]
+    fn poll_accept(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<io::Result<usize>> {
+        match self.accept(buf) {
+            Some(count) => std::task::Poll::Ready(Ok(count)),
+            None => {
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+        }
+    }
+}
+
+impl Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.accept(buf)
+            .ok_or_else(|| io::ErrorKind::WouldBlock.into())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncWrite for Trickle {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<io::Result<usize>> {
+        self.get_mut().poll_accept(cx, buf)
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl futures::io::AsyncWrite for Trickle {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<io::Result<usize>> {
+        self.get_mut().poll_accept(cx, buf)
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+/// Times out on the second and third call, takes everything otherwise.
+#[derive(Default)]
+struct Flaky {
+    data: Vec<u8>,
+    calls: usize,
+}
+
+impl Flaky {
+    fn accept(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.calls += 1;
+        if matches!(self.calls, 2 | 3) {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+}
+
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.accept(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncWrite for Flaky {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<io::Result<usize>> {
+        std::task::Poll::Ready(self.get_mut().accept(buf))
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+fn plaintext() -> Vec<u8> {
+    (0..=255).cycle().take(100).collect()
+}
+
+fn expected_ciphertext() -> Vec<u8> {
+    let mut data = plaintext();
+    DefaultCipher::new(&KEY, Direction::Encrypt)
+        .unwrap()
+        .apply(&mut data)
+        .unwrap();
+    data
+}
+
+#[test]
+fn sync_partial_writes() {
+    let mut writer = CFB8WriteHalf::new(Trickle::default(), &KEY).unwrap();
+
+    for chunk in plaintext().chunks(10) {
+        loop {
+            match writer.write(chunk) {
+                Ok(count) => {
+                    assert_eq!(count, chunk.len());
+                    break;
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => panic!("{e}"),
+            }
+        }
+    }
+    while let Err(e) = writer.flush() {
+        assert_eq!(e.kind(), io::ErrorKind::WouldBlock);
+    }
+
+    assert_eq!(writer.into_inner().data, expected_ciphertext());
+}
+
+#[test]
+fn sync_error_is_returned_after_encrypting() {
+    let plaintext = plaintext();
+    let chunks: Vec<_> = plaintext.chunks(40).collect();
+    let mut writer = CFB8WriteHalf::new(Flaky::default(), &KEY).unwrap();
+
+    assert_eq!(writer.write(chunks[0]).unwrap(), 40);
+    // Encrypted and kept even though sending it failed
+    assert_eq!(
+        writer.write(chunks[1]).unwrap_err().kind(),
+        io::ErrorKind::TimedOut
+    );
+    // Retried by flush instead of writing the chunk again
+    assert_eq!(writer.flush().unwrap_err().kind(), io::ErrorKind::TimedOut);
+    writer.flush().unwrap();
+    writer.write_all(chunks[2]).unwrap();
+    writer.flush().unwrap();
+
+    assert_eq!(writer.into_inner().data, expected_ciphertext());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_error_is_returned_after_encrypting() {
+    use tokio::io::AsyncWriteExt;
+
+    let plaintext = plaintext();
+    let chunks: Vec<_> = plaintext.chunks(40).collect();
+    let mut writer = CFB8WriteHalf::new(Flaky::default(), &KEY).unwrap();
+
+    assert_eq!(
+        AsyncWriteExt::write(&mut writer, chunks[0]).await.unwrap(),
+        40
+    );
+    assert_eq!(
+        AsyncWriteExt::write(&mut writer, chunks[1])
+            .await
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::TimedOut
+    );
+    assert_eq!(
+        AsyncWriteExt::flush(&mut writer).await.unwrap_err().kind(),
+        io::ErrorKind::TimedOut
+    );
+    AsyncWriteExt::flush(&mut writer).await.unwrap();
+    AsyncWriteExt::write_all(&mut writer, chunks[2])
+        .await
+        .unwrap();
+    AsyncWriteExt::flush(&mut writer).await.unwrap();
+
+    assert_eq!(writer.into_inner().data, expected_ciphertext());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_partial_writes() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = CFB8WriteHalf::new(Trickle::default(), &KEY).unwrap();
+    for chunk in plaintext().chunks(10) {
+        AsyncWriteExt::write_all(&mut writer, chunk).await.unwrap();
+    }
+    AsyncWriteExt::flush(&mut writer).await.unwrap();
+
+    assert_eq!(writer.into_inner().data, expected_ciphertext());
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn futures_partial_writes() {
+    use futures::AsyncWriteExt;
+
+    futures::executor::block_on(async {
+        let mut writer = CFB8WriteHalf::new(Trickle::default(), &KEY).unwrap();
+        for chunk in plaintext().chunks(10) {
+            AsyncWriteExt::write_all(&mut writer, chunk).await.unwrap();
+        }
+        AsyncWriteExt::flush(&mut writer).await.unwrap();
+
+        assert_eq!(writer.into_inner().data, expected_ciphertext());
+    });
+}
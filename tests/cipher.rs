@@ -0,0 +1,84 @@
+#![cfg(any(feature = "openssl", feature = "rustcrypto"))]
+
+use minecraft_protocol::cipher::{Cfb8Cipher, DefaultCipher, Direction};
+
+// Produced with `openssl enc -aes-128-cfb8`, key and IV equal
+const VECTORS: &[(&[u8; 16], &[u8], &[u8])] = &[
+    (
+        &[0; 16],
+        &[0; 20],
+        &[
+            0x66, 0x16, 0xf9, 0x2e, 0x42, 0xa8, 0xf1, 0x1a, 0x91, 0x16, 0x68, 0x57, 0x8e, 0xc3,
+            0xaa, 0x0f, 0x93, 0x00, 0x4a, 0xab,
+        ],
+    ),
+    (
+        b"0123456789abcdef",
+        b"The quick brown fox jumps over the lazy dog",
+        &[
+            0x26, 0x86, 0x54, 0x0e, 0x23, 0x13, 0x0d, 0xe1, 0x91, 0xbc, 0x82, 0x16, 0x6f, 0x1b,
+            0xab, 0x04, 0xe2, 0xcf, 0xf6, 0x8c, 0x51, 0x7c, 0x28, 0x89, 0x6f, 0x1f, 0x1c, 0xf1,
+            0x27, 0xcf, 0xb9, 0x27, 0x2f, 0x84, 0x8b, 0xfb, 0xfb, 0x3c, 0x09, 0xe3, 0x8b, 0x90,
+            0xda,
+        ],
+    ),
+];
+
+fn check_vectors<C: Cfb8Cipher>() {
+    for (key, plaintext, ciphertext) in VECTORS {
+        let mut data = plaintext.to_vec();
+        C::new(key, Direction::Encrypt)
+            .unwrap()
+            .apply(&mut data)
+            .unwrap();
+        assert_eq!(data, *ciphertext);
+
+        // Byte by byte must give the same stream as one call
+        let mut decrypter = C::new(key, Direction::Decrypt).unwrap();
+        for byte in data.chunks_mut(1) {
+            decrypter.apply(byte).unwrap();
+        }
+        assert_eq!(data, *plaintext);
+    }
+}
+
+#[test]
+fn default_cipher_vectors() {
+    check_vectors::<DefaultCipher>();
+}
+
+#[cfg(feature = "openssl")]
+#[test]
+fn openssl_vectors() {
+    check_vectors::<minecraft_protocol::cipher::OpensslCipher>();
+}
+
+#[cfg(feature = "rustcrypto")]
+#[test]
+fn rustcrypto_vectors() {
+    check_vectors::<minecraft_protocol::cipher::RustCryptoCipher>();
+}
+
+#[cfg(all(feature = "openssl", feature = "rustcrypto"))]
+#[test]
+fn backends_agree_across_chunks() {
+    use minecraft_protocol::cipher::{OpensslCipher, RustCryptoCipher};
+
+    let key: [u8; 16] = std::array::from_fn(|i| i as u8 * 17);
+    let plaintext: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+    let mut openssl = OpensslCipher::new(&key, Direction::Encrypt).unwrap();
+    let mut rustcrypto = RustCryptoCipher::new(&key, Direction::Encrypt).unwrap();
+
+    let mut left = plaintext.clone();
+    let mut right = plaintext.clone();
+    for (a, b) in left.chunks_mut(7).zip(right.chunks_mut(7)) {
+        openssl.apply(a).unwrap();
+        rustcrypto.apply(b).unwrap();
+    }
+    assert_eq!(left, right);
+
+    let mut decrypter = RustCryptoCipher::new(&key, Direction::Decrypt).unwrap();
+    decrypter.apply(&mut left).unwrap();
+    assert_eq!(left, plaintext);
+}
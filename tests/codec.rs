@@ -0,0 +1,133 @@
+#![cfg(feature = "tokio")]
+
+use bytes::BytesMut;
+use futures::{SinkExt, StreamExt};
+use minecraft_protocol::{
+    codec::MinecraftCodec,
+    packet::{PacketError, UncompressedPacket},
+    varint::VarInt,
+};
+use tokio_util::codec::{Decoder, Encoder, Framed};
+
+fn packet(id: i32, payload: &[u8]) -> UncompressedPacket {
+    UncompressedPacket {
+        packet_id: VarInt(id),
+        payload: payload.to_vec(),
+    }
+}
+
+#[test]
+fn uncompressed_framing() {
+    let mut codec = MinecraftCodec::new();
+    let mut buf = BytesMut::new();
+    codec.encode(&packet(0x01, b"abc"), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x04\x01abc");
+
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(packet(0x01, b"abc")));
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn partial_frames_wait_for_more_bytes() {
+    let mut codec = MinecraftCodec::new();
+    let mut encoded = BytesMut::new();
+    let large = packet(0x02, &[7; 300]);
+    codec.encode(&large, &mut encoded).unwrap();
+    codec.encode(&packet(0x03, b""), &mut encoded).unwrap();
+
+    let mut buf = BytesMut::new();
+    let mut decoded = Vec::new();
+    for byte in encoded {
+        buf.extend_from_slice(&[byte]);
+        if let Some(packet) = codec.decode(&mut buf).unwrap() {
+            decoded.push(packet);
+        }
+    }
+
+    assert_eq!(decoded, vec![large, packet(0x03, b"")]);
+}
+
+#[test]
+fn compression_threshold() {
+    let mut codec = MinecraftCodec::new().with_compression(Some(64));
+    let small = packet(0x01, b"hi");
+    let large = packet(0x02, &[0; 1000]);
+
+    let mut buf = BytesMut::new();
+    codec.encode(&small, &mut buf).unwrap();
+    // Below the threshold: data length 0 followed by the packet as is
+    assert_eq!(&buf[..], b"\x04\x00\x01hi");
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(small));
+
+    codec.encode(&large, &mut buf).unwrap();
+    assert!(buf.len() < 100);
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(large));
+}
+
+#[test]
+fn switching_compression_keeps_buffered_bytes() {
+    let mut plain = MinecraftCodec::new();
+    let mut compressed = MinecraftCodec::new().with_compression(Some(0));
+
+    let mut buf = BytesMut::new();
+    plain.encode(&packet(0x03, b"\x00"), &mut buf).unwrap();
+    compressed
+        .encode(&packet(0x26, b"keepalive"), &mut buf)
+        .unwrap();
+
+    let mut codec = MinecraftCodec::new();
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(packet(0x03, b"\x00")));
+    codec.set_compression(Some(0));
+    assert_eq!(
+        codec.decode(&mut buf).unwrap(),
+        Some(packet(0x26, b"keepalive"))
+    );
+}
+
+#[test]
+fn negative_threshold_disables_compression() {
+    let codec = MinecraftCodec::new().with_compression(Some(-1));
+    assert_eq!(codec.compression(), None);
+}
+
+#[test]
+fn rejects_oversized_frames() {
+    let mut codec = MinecraftCodec::new().with_max_frame_size(16);
+
+    // Only the length prefix has arrived, that's enough to reject the frame
+    let mut buf = BytesMut::from(&b"\x80\x01"[..]);
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(PacketError::FrameTooLarge(128, 16))
+    ));
+
+    let mut buf = BytesMut::new();
+    assert!(matches!(
+        codec.encode(&packet(0x00, &[0; 32]), &mut buf),
+        Err(PacketError::FrameTooLarge(33, 16))
+    ));
+}
+
+#[test]
+fn rejects_bad_data_length() {
+    let mut codec = MinecraftCodec::new().with_compression(Some(256));
+
+    // Claims compression for a packet below the threshold
+    let mut buf = BytesMut::from(&b"\x03\x10\x00\x00"[..]);
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(PacketError::InvalidDataLength(16))
+    ));
+}
+
+#[tokio::test]
+async fn framed_round_trip() {
+    let (client, server) = tokio::io::duplex(64);
+    let mut client = Framed::new(client, MinecraftCodec::new());
+    let mut server = Framed::new(server, MinecraftCodec::new());
+
+    let sent = packet(0x00, &[1; 500]);
+    let (send, received) = tokio::join!(client.send(&sent), server.next());
+    send.unwrap();
+    assert_eq!(received.unwrap().unwrap(), sent);
+}
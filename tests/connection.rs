@@ -0,0 +1,163 @@
+#![cfg(feature = "tokio")]
+
+use minecraft_protocol::{
+    connection::{Connection, ConnectionError, State},
+    packet::UncompressedPacket,
+    packets::{
+        handshake::serverbound::Handshake,
+        login::serverbound::{LoginAcknowledged, LoginStart},
+    },
+    varint::VarInt,
+};
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+use minecraft_protocol::{
+    packets::login::clientbound::{LoginSuccess, SetCompression},
+    ser::PrefixedArray,
+};
+use tokio::io::DuplexStream;
+
+fn pair() -> (Connection<DuplexStream>, Connection<DuplexStream>) {
+    let (client, server) = tokio::io::duplex(4096);
+    (Connection::client(client), Connection::server(server))
+}
+
+fn handshake(next_state: VarInt) -> Handshake {
+    Handshake {
+        protocol_version: VarInt(767),
+        server_address: "localhost".to_string(),
+        server_port: 25565,
+        next_state,
+    }
+}
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+fn login_success(username: &str) -> LoginSuccess {
+    LoginSuccess {
+        uuid: 1,
+        username: username.to_string(),
+        properties: PrefixedArray(vec![]),
+        strict_error_handling: false,
+    }
+}
+
+#[tokio::test]
+async fn handshake_moves_both_sides() {
+    let (mut client, mut server) = pair();
+
+    client.send(&handshake(Handshake::STATUS)).await.unwrap();
+    assert_eq!(client.state(), State::Status);
+
+    let received: Handshake = server.recv().await.unwrap();
+    assert_eq!(received.next_state, Handshake::STATUS);
+    assert_eq!(server.state(), State::Status);
+}
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+#[tokio::test]
+async fn login_with_compression_and_encryption() {
+    let (mut client, mut server) = pair();
+    let key = [7; 16];
+
+    client.send(&handshake(Handshake::LOGIN)).await.unwrap();
+    client
+        .send(&LoginStart {
+            name: "Steve".to_string(),
+            uuid: 1,
+        })
+        .await
+        .unwrap();
+    server.recv::<Handshake>().await.unwrap();
+    server.recv::<LoginStart>().await.unwrap();
+    assert_eq!(server.state(), State::Login);
+
+    server.enable_encryption(&key).unwrap();
+    client.enable_encryption(&key).unwrap();
+
+    // Set Compression and a compressed packet back to back
+    server
+        .send(&SetCompression {
+            threshold: VarInt(16),
+        })
+        .await
+        .unwrap();
+    assert_eq!(server.compression(), Some(16));
+    let success = login_success(&"x".repeat(64));
+    server.send(&success).await.unwrap();
+
+    client.recv::<SetCompression>().await.unwrap();
+    assert_eq!(client.compression(), Some(16));
+    assert_eq!(client.recv::<LoginSuccess>().await.unwrap(), success);
+
+    client.send(&LoginAcknowledged).await.unwrap();
+    server.recv::<LoginAcknowledged>().await.unwrap();
+    assert_eq!(client.state(), State::Configuration);
+    assert_eq!(server.state(), State::Configuration);
+}
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+#[tokio::test]
+async fn encryption_applies_to_buffered_bytes() {
+    let (mut client, mut server) = pair();
+    let key = [3; 16];
+    client.set_state(State::Login);
+    server.set_state(State::Login);
+
+    server
+        .send(&SetCompression {
+            threshold: VarInt(-1),
+        })
+        .await
+        .unwrap();
+    server.enable_encryption(&key).unwrap();
+    server.send(&login_success("Alex")).await.unwrap();
+
+    // Both packets are in the client's buffer after this read
+    client.recv::<SetCompression>().await.unwrap();
+    assert_eq!(client.compression(), None);
+
+    client.enable_encryption(&key).unwrap();
+    assert_eq!(
+        client.recv::<LoginSuccess>().await.unwrap(),
+        login_success("Alex")
+    );
+}
+
+#[tokio::test]
+async fn wrong_packet_id_is_rejected() {
+    let (mut client, mut server) = pair();
+    client.set_state(State::Login);
+    server.set_state(State::Login);
+
+    client.send(&LoginAcknowledged).await.unwrap();
+    assert!(matches!(
+        server.recv::<LoginStart>().await,
+        Err(ConnectionError::UnexpectedPacket {
+            expected: 0x00,
+            actual: 0x03
+        })
+    ));
+}
+
+#[tokio::test]
+async fn closed_between_packets() {
+    let (client, mut server) = pair();
+    drop(client);
+
+    assert!(matches!(
+        server.recv_raw().await,
+        Err(ConnectionError::Closed)
+    ));
+}
+
+#[tokio::test]
+async fn raw_packets_pass_through() {
+    let (mut client, mut server) = pair();
+    let packet = UncompressedPacket {
+        packet_id: VarInt(0x42),
+        payload: vec![1, 2, 3],
+    };
+
+    client.send_raw(&packet).await.unwrap();
+    assert_eq!(server.recv_raw().await.unwrap(), packet);
+    assert_eq!(server.state(), State::Handshaking);
+}
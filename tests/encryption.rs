@@ -0,0 +1,78 @@
+#![cfg(any(feature = "openssl", feature = "rustcrypto"))]
+
+use minecraft_protocol::encryption::{
+    EncryptionError, RsaKeyPair, encrypt_with_public_key, encryption_response,
+    generate_shared_secret, generate_verify_token, server_hash, shared_secret_key,
+};
+
+#[test]
+fn key_exchange_roundtrip() {
+    let key_pair = RsaKeyPair::generate().unwrap();
+    // SubjectPublicKeyInfo of a 1024-bit key
+    assert_eq!(key_pair.public_key().len(), 162);
+    assert_eq!(key_pair.public_key()[0], 0x30);
+
+    let verify_token = generate_verify_token().unwrap();
+    let request = key_pair.encryption_request(&verify_token, true);
+    assert_eq!(request.server_id, "");
+
+    let shared_secret = generate_shared_secret().unwrap();
+    let response = encryption_response(&request, &shared_secret).unwrap();
+    assert_eq!(response.shared_secret.len(), 128);
+    assert_eq!(response.verify_token.len(), 128);
+
+    assert_eq!(
+        key_pair.decrypt_response(&response, &verify_token).unwrap(),
+        shared_secret
+    );
+}
+
+#[test]
+fn rejects_wrong_verify_token_and_secret_length() {
+    let key_pair = RsaKeyPair::generate().unwrap();
+    let request = key_pair.encryption_request(&[1, 2, 3, 4], false);
+    let response = encryption_response(&request, &[0; 16]).unwrap();
+
+    assert!(matches!(
+        key_pair.decrypt_response(&response, &[4, 3, 2, 1]),
+        Err(EncryptionError::VerifyTokenMismatch)
+    ));
+
+    let mut response = response;
+    response.shared_secret = encrypt_with_public_key(key_pair.public_key(), &[0; 15]).unwrap();
+    assert!(matches!(
+        key_pair.decrypt_response(&response, &[1, 2, 3, 4]),
+        Err(EncryptionError::InvalidSharedSecret(15))
+    ));
+}
+
+#[test]
+fn shared_secrets_are_random() {
+    assert_ne!(
+        generate_shared_secret().unwrap(),
+        generate_shared_secret().unwrap()
+    );
+    assert!(shared_secret_key(&[7; 16]).is_ok());
+    assert!(shared_secret_key(&[7; 17]).is_err());
+}
+
+#[test]
+fn server_hash_vectors() {
+    assert_eq!(
+        server_hash("Notch", &[], &[]),
+        "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48"
+    );
+    assert_eq!(
+        server_hash("jeb_", &[], &[]),
+        "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1"
+    );
+    assert_eq!(
+        server_hash("simon", &[], &[]),
+        "88e16a1019277b15d58faf0541e11910eb756f6"
+    );
+    // The parts are hashed back to back
+    assert_eq!(
+        server_hash("No", b"tc", b"h"),
+        server_hash("Notch", &[], &[])
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "futures-io")]
+
+use futures::{executor::block_on, io::Cursor};
+use minecraft_protocol::{
+    packet::RawPacket,
+    varint::{VarInt, VarLong},
+};
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+const KEY: [u8; 16] = *b"0123456789abcdef";
+
+#[test]
+fn varints() {
+    block_on(async {
+        let mut buf = Vec::new();
+        VarInt(-1).write_futures(&mut buf).await.unwrap();
+        VarLong(i64::MAX).write_futures(&mut buf).await.unwrap();
+        assert_eq!(buf[..5], VarInt(-1).to_bytes());
+
+        let mut reader = Cursor::new(buf);
+        assert_eq!(VarInt::read_futures(&mut reader).await.unwrap(), VarInt(-1));
+        assert_eq!(
+            VarLong::read_futures(&mut reader).await.unwrap(),
+            VarLong(i64::MAX)
+        );
+    });
+}
+
+#[test]
+fn raw_packet_matches_sync() {
+    block_on(async {
+        let packet = RawPacket {
+            data: vec![0x00, 1, 2, 3],
+        };
+
+        let mut buf = Vec::new();
+        packet.write_futures(&mut buf).await.unwrap();
+
+        let mut sync_buf = Vec::new();
+        packet.write_sync(&mut sync_buf).unwrap();
+        assert_eq!(buf, sync_buf);
+
+        let read = RawPacket::read_futures(&mut Cursor::new(buf))
+            .await
+            .unwrap();
+        assert_eq!(read.data, packet.data);
+    });
+}
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+#[test]
+fn cfb8_matches_sync() {
+    use futures::{AsyncReadExt, AsyncWriteExt};
+    use minecraft_protocol::cfb8_stream::{CFB8Stream, CFB8WriteHalf};
+
+    block_on(async {
+        let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+        let mut writer = CFB8WriteHalf::new(Cursor::new(Vec::new()), &KEY).unwrap();
+        writer.write_all(plaintext).await.unwrap();
+        let ciphertext = writer.into_inner().into_inner();
+
+        let mut sync_writer = CFB8WriteHalf::new(Vec::new(), &KEY).unwrap();
+        std::io::Write::write_all(&mut sync_writer, plaintext).unwrap();
+        assert_eq!(ciphertext, sync_writer.into_inner());
+
+        let mut stream = CFB8Stream::new(Cursor::new(ciphertext), Vec::<u8>::new(), &KEY).unwrap();
+        let mut decrypted = Vec::new();
+        stream.read_to_end(&mut decrypted).await.unwrap();
+        assert_eq!(decrypted, plaintext);
+    });
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "v1_21")]
+
+use minecraft_protocol::{
+    generated::v1_21::{handshaking, login, status},
+    ser::RestBuffer,
+    varint::VarInt,
+};
+
+#[test]
+fn generated_packets_round_trip() {
+    let handshake = handshaking::serverbound::SetProtocol {
+        protocol_version: VarInt(767),
+        server_host: "localhost".to_string(),
+        server_port: 25565,
+        next_state: VarInt(2),
+    };
+    let packet = handshake.as_uncompressed().unwrap();
+    assert_eq!(packet.packet_id, VarInt(0x00));
+    assert_eq!(
+        packet
+            .convert::<handshaking::serverbound::SetProtocol>()
+            .unwrap(),
+        handshake
+    );
+
+    let ping = status::clientbound::Ping { time: 42 };
+    let packet = ping.as_uncompressed().unwrap();
+    assert_eq!(packet.packet_id, VarInt(0x01));
+    assert_eq!(packet.convert::<status::clientbound::Ping>().unwrap(), ping);
+
+    let response = login::serverbound::LoginPluginResponse {
+        message_id: VarInt(7),
+        data: Some(RestBuffer(vec![1, 2, 3])),
+    };
+    let packet = response.as_uncompressed().unwrap();
+    assert_eq!(packet.payload, [7, 1, 1, 2, 3]);
+    assert_eq!(
+        packet
+            .convert::<login::serverbound::LoginPluginResponse>()
+            .unwrap(),
+        response
+    );
+
+    assert_eq!(login::clientbound::Compress::PACKET_ID, VarInt(0x03));
+    assert_eq!(
+        login::serverbound::LoginAcknowledged::PACKET_ID,
+        VarInt(0x03)
+    );
+}
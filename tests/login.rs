@@ -0,0 +1,410 @@
+#![cfg(all(feature = "tokio", any(feature = "openssl", feature = "rustcrypto")))]
+
+use std::{io, sync::Mutex};
+
+use minecraft_protocol::{
+    connection::{Connection, State},
+    encryption::{RsaKeyPair, server_hash},
+    login::{
+        Authenticator, LoginError, OfflineAuth, OfflineAuthenticator, ServerLoginOptions,
+        SessionAuth, SessionAuthenticator, client_login, server_login,
+    },
+    packet::UncompressedPacket,
+    packets::{
+        handshake::serverbound::Handshake,
+        login::{
+            clientbound::{CookieRequest, Disconnect, LoginPluginRequest, SetCompression},
+            serverbound::{
+                CookieResponse, EncryptionResponse, LoginAcknowledged, LoginPluginResponse,
+                LoginStart,
+            },
+        },
+    },
+    profile::{GameProfile, offline_uuid},
+    ser::RestBuffer,
+    session::{HttpResponse, HttpTransport, SessionClient},
+    varint::VarInt,
+};
+use tokio::io::{DuplexStream, duplex};
+
+const NOTCH: u128 = 0x069a79f4_44e9_4726_a5be_fca90e38aaf5;
+
+/// Accepts every `join` and records its body. `hasJoined` answers with
+/// `profile`, or 204 if there is none.
+#[derive(Default)]
+struct MockTransport {
+    joins: Mutex<Vec<String>>,
+    profile: Option<String>,
+}
+
+impl HttpTransport for MockTransport {
+    async fn get(&self, _url: &str) -> io::Result<HttpResponse> {
+        Ok(match &self.profile {
+            Some(profile) => HttpResponse {
+                status: 200,
+                body: profile.clone(),
+            },
+            None => HttpResponse {
+                status: 204,
+                body: String::new(),
+            },
+        })
+    }
+
+    async fn post_json(&self, _url: &str, body: &str) -> io::Result<HttpResponse> {
+        self.joins.lock().unwrap().push(body.to_string());
+        Ok(HttpResponse {
+            status: 204,
+            body: String::new(),
+        })
+    }
+}
+
+fn connections() -> (Connection<DuplexStream>, Connection<DuplexStream>) {
+    let (client, server) = duplex(1 << 16);
+    (Connection::client(client), Connection::server(server))
+}
+
+async fn read_login_start(server: &mut Connection<DuplexStream>) -> LoginStart {
+    let handshake: Handshake = server.recv().await.unwrap();
+    assert_eq!(handshake.next_state, Handshake::LOGIN);
+    assert_eq!(handshake.server_address, "localhost");
+    server.recv().await.unwrap()
+}
+
+async fn finish_login(server: &mut Connection<DuplexStream>, start: &LoginStart) {
+    server
+        .send(&GameProfile::new(start.uuid, start.name.clone()).login_success())
+        .await
+        .unwrap();
+    server.recv::<LoginAcknowledged>().await.unwrap();
+    assert_eq!(server.state(), State::Configuration);
+}
+
+#[tokio::test]
+async fn offline_login_with_compression_and_plugins() {
+    let (mut client, mut server) = connections();
+
+    let server_task = tokio::spawn(async move {
+        let start = read_login_start(&mut server).await;
+        server
+            .send(&SetCompression {
+                threshold: VarInt(64),
+            })
+            .await
+            .unwrap();
+
+        server
+            .send(&LoginPluginRequest {
+                message_id: VarInt(7),
+                channel: "velocity:player_info".to_string(),
+                data: RestBuffer(vec![1]),
+            })
+            .await
+            .unwrap();
+        let response: LoginPluginResponse = server.recv().await.unwrap();
+        assert_eq!(response.message_id, VarInt(7));
+        assert_eq!(response.data, None);
+
+        server
+            .send(&CookieRequest {
+                key: "minecraft:session".to_string(),
+            })
+            .await
+            .unwrap();
+        let response: CookieResponse = server.recv().await.unwrap();
+        assert_eq!(response.payload, None);
+
+        finish_login(&mut server, &start).await;
+        start
+    });
+
+    let profile = GameProfile::new(NOTCH, "Notch");
+    let logged_in = client_login(&mut client, "localhost", 25565, &profile, &OfflineAuth)
+        .await
+        .unwrap();
+
+    assert_eq!(logged_in, profile);
+    assert_eq!(client.state(), State::Configuration);
+    assert_eq!(client.compression(), Some(64));
+    assert!(!client.is_encrypted());
+
+    let start = server_task.await.unwrap();
+    assert_eq!((start.name.as_str(), start.uuid), ("Notch", NOTCH));
+}
+
+#[tokio::test]
+async fn online_login_joins_and_encrypts() {
+    let (mut client, mut server) = connections();
+    let keys = RsaKeyPair::generate().unwrap();
+
+    let server_task = tokio::spawn(async move {
+        let start = read_login_start(&mut server).await;
+        let token = [1, 2, 3, 4];
+        server
+            .send(&keys.encryption_request(&token, true))
+            .await
+            .unwrap();
+
+        let response: EncryptionResponse = server.recv().await.unwrap();
+        let secret = keys.decrypt_response(&response, &token).unwrap();
+        server.enable_encryption(&secret).unwrap();
+
+        finish_login(&mut server, &start).await;
+        server_hash("", &secret, keys.public_key())
+    });
+
+    let session = SessionClient::new(MockTransport::default());
+    let auth = SessionAuth {
+        session: &session,
+        access_token: "token",
+    };
+    let profile = GameProfile::new(NOTCH, "Notch");
+    client_login(&mut client, "localhost", 25565, &profile, &auth)
+        .await
+        .unwrap();
+    assert!(client.is_encrypted());
+
+    let hash = server_task.await.unwrap();
+    let joins = session.transport().joins.lock().unwrap().clone();
+    let join: serde_json::Value = serde_json::from_str(&joins[0]).unwrap();
+    assert_eq!(join["serverId"], hash);
+    assert_eq!(join["selectedProfile"], "069a79f444e94726a5befca90e38aaf5");
+}
+
+#[tokio::test]
+async fn offline_client_rejects_online_server() {
+    let (mut client, mut server) = connections();
+    let keys = RsaKeyPair::generate().unwrap();
+
+    tokio::spawn(async move {
+        read_login_start(&mut server).await;
+        server
+            .send(&keys.encryption_request(&[0; 4], true))
+            .await
+            .unwrap();
+        server
+    });
+
+    let profile = GameProfile::new(NOTCH, "Notch");
+    assert!(matches!(
+        client_login(&mut client, "localhost", 25565, &profile, &OfflineAuth).await,
+        Err(LoginError::AuthenticationRequired)
+    ));
+}
+
+#[tokio::test]
+async fn disconnect_is_a_typed_error() {
+    let (mut client, mut server) = connections();
+
+    tokio::spawn(async move {
+        read_login_start(&mut server).await;
+        server
+            .send(&Disconnect {
+                reason: r#"{"text":"You are ","extra":["banned"]}"#.to_string(),
+            })
+            .await
+            .unwrap();
+        server
+    });
+
+    let profile = GameProfile::new(NOTCH, "Notch");
+    let error = client_login(&mut client, "localhost", 25565, &profile, &OfflineAuth)
+        .await
+        .unwrap_err();
+    let LoginError::Disconnected(reason) = &error else {
+        panic!("{error}");
+    };
+    assert_eq!(reason["extra"][0], "banned");
+    assert_eq!(error.to_string(), "Disconnected: You are banned");
+}
+
+#[tokio::test]
+async fn unexpected_packet() {
+    let (mut client, mut server) = connections();
+
+    tokio::spawn(async move {
+        read_login_start(&mut server).await;
+        server
+            .send_raw(&UncompressedPacket {
+                packet_id: VarInt(0x7F),
+                payload: Vec::new(),
+            })
+            .await
+            .unwrap();
+        server
+    });
+
+    let profile = GameProfile::new(NOTCH, "Notch");
+    assert!(matches!(
+        client_login(&mut client, "localhost", 25565, &profile, &OfflineAuth).await,
+        Err(LoginError::UnexpectedPacket(0x7F))
+    ));
+}
+
+mod server {
+    use super::*;
+
+    #[tokio::test]
+    async fn offline_login_end_to_end() {
+        let (mut client, mut server) = connections();
+
+        let server_task = tokio::spawn(async move {
+            let _: Handshake = server.recv().await.unwrap();
+            let options = ServerLoginOptions {
+                compression_threshold: Some(128),
+                ..Default::default()
+            };
+            let profile = server_login(&mut server, &options, &OfflineAuthenticator)
+                .await
+                .unwrap();
+            assert_eq!(server.state(), State::Configuration);
+            assert_eq!(server.compression(), Some(128));
+            profile
+        });
+
+        let profile = GameProfile::new(1, "Notch");
+        let logged_in = client_login(&mut client, "localhost", 25565, &profile, &OfflineAuth)
+            .await
+            .unwrap();
+        assert_eq!(logged_in, GameProfile::offline("Notch"));
+        assert_eq!(logged_in.id, offline_uuid("Notch"));
+        assert_eq!(client.compression(), Some(128));
+        assert_eq!(server_task.await.unwrap(), logged_in);
+    }
+
+    #[tokio::test]
+    async fn online_login_end_to_end() {
+        let (mut client, mut server) = connections();
+        let verified = r#"{"id":"069a79f444e94726a5befca90e38aaf5","name":"Notch","properties":[{"name":"textures","value":"e30="}]}"#;
+
+        let server_task = tokio::spawn(async move {
+            let _: Handshake = server.recv().await.unwrap();
+            let keys = RsaKeyPair::generate().unwrap();
+            let authenticator = SessionAuthenticator {
+                session: SessionClient::new(MockTransport {
+                    profile: Some(verified.to_string()),
+                    ..Default::default()
+                }),
+            };
+            let options = ServerLoginOptions {
+                key_pair: Some(&keys),
+                ..Default::default()
+            };
+            let profile = server_login(&mut server, &options, &authenticator)
+                .await
+                .unwrap();
+            assert!(server.is_encrypted());
+            profile
+        });
+
+        let session = SessionClient::new(MockTransport::default());
+        let auth = SessionAuth {
+            session: &session,
+            access_token: "token",
+        };
+        let profile = GameProfile::new(NOTCH, "Notch");
+        let logged_in = client_login(&mut client, "localhost", 25565, &profile, &auth)
+            .await
+            .unwrap();
+
+        assert_eq!(session.transport().joins.lock().unwrap().len(), 1);
+        assert_eq!(logged_in.properties[0].value, "e30=");
+        assert_eq!(server_task.await.unwrap(), logged_in);
+    }
+
+    #[tokio::test]
+    async fn unverified_player_is_kicked() {
+        let (mut client, mut server) = connections();
+
+        let server_task = tokio::spawn(async move {
+            let _: Handshake = server.recv().await.unwrap();
+            let keys = RsaKeyPair::generate().unwrap();
+            let authenticator = SessionAuthenticator {
+                session: SessionClient::new(MockTransport::default()),
+            };
+            let options = ServerLoginOptions {
+                key_pair: Some(&keys),
+                ..Default::default()
+            };
+            server_login(&mut server, &options, &authenticator).await
+        });
+
+        let session = SessionClient::new(MockTransport::default());
+        let auth = SessionAuth {
+            session: &session,
+            access_token: "token",
+        };
+        let profile = GameProfile::new(NOTCH, "Notch");
+        let error = client_login(&mut client, "localhost", 25565, &profile, &auth)
+            .await
+            .unwrap_err();
+        let LoginError::Disconnected(reason) = error else {
+            panic!("{error}");
+        };
+        assert_eq!(
+            reason["translate"],
+            "multiplayer.disconnect.unverified_username"
+        );
+        assert!(matches!(
+            server_task.await.unwrap(),
+            Err(LoginError::Session(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn check_rejects_player() {
+        let (mut client, mut server) = connections();
+
+        let server_task = tokio::spawn(async move {
+            let _: Handshake = server.recv().await.unwrap();
+            let authenticator =
+                OfflineAuthenticator.with_check(|profile| match profile.name.as_str() {
+                    "Herobrine" => Err("You are banned".into()),
+                    _ => Ok(()),
+                });
+            server_login(&mut server, &ServerLoginOptions::default(), &authenticator).await
+        });
+
+        let profile = GameProfile::new(0, "Herobrine");
+        let error = client_login(&mut client, "localhost", 25565, &profile, &OfflineAuth)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Disconnected: You are banned");
+        assert!(matches!(
+            server_task.await.unwrap(),
+            Err(LoginError::Disconnected(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn session_authenticator_needs_key_pair() {
+        let (mut client, mut server) = connections();
+
+        client
+            .send(&Handshake {
+                protocol_version: VarInt(client.protocol_version()),
+                server_address: "localhost".to_string(),
+                server_port: 25565,
+                next_state: Handshake::LOGIN,
+            })
+            .await
+            .unwrap();
+        server.recv::<Handshake>().await.unwrap();
+        client
+            .send(&LoginStart {
+                name: "Notch".to_string(),
+                uuid: NOTCH,
+            })
+            .await
+            .unwrap();
+
+        let authenticator = SessionAuthenticator {
+            session: SessionClient::new(MockTransport::default()),
+        };
+        assert!(matches!(
+            server_login(&mut server, &ServerLoginOptions::default(), &authenticator).await,
+            Err(LoginError::MissingKeyPair)
+        ));
+    }
+}
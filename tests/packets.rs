@@ -0,0 +1,224 @@
+use std::io::Cursor;
+
+use minecraft_protocol::{
+    nbt::Nbt,
+    packets::{
+        configuration::{self, KnownPack},
+        handshake::serverbound::Handshake,
+        login::{self, clientbound::Property},
+        status,
+    },
+    ser::{Deserialize, PrefixedArray, RestBuffer, SerializationError},
+    varint::VarInt,
+};
+
+#[test]
+fn handshake_round_trip() {
+    let handshake = Handshake {
+        protocol_version: VarInt(767),
+        server_address: "mc.example.com".to_string(),
+        server_port: 25565,
+        next_state: Handshake::LOGIN,
+    };
+
+    let packet = handshake.as_uncompressed().unwrap();
+    assert_eq!(packet.packet_id, VarInt(0x00));
+    assert_eq!(packet.convert::<Handshake>().unwrap(), handshake);
+}
+
+#[test]
+fn status_packets() {
+    let request = status::serverbound::StatusRequest
+        .as_uncompressed()
+        .unwrap();
+    assert_eq!(request.packet_id, VarInt(0x00));
+    assert!(request.payload.is_empty());
+
+    let ping = status::serverbound::PingRequest { payload: 7 }
+        .as_uncompressed()
+        .unwrap();
+    let pong = ping.convert::<status::clientbound::PongResponse>().unwrap();
+    assert_eq!(pong.payload, 7);
+}
+
+#[test]
+fn login_success_wire_format() {
+    let success = login::clientbound::LoginSuccess {
+        uuid: 0x0123456789abcdef0123456789abcdef,
+        username: "Notch".to_string(),
+        properties: PrefixedArray(vec![Property {
+            name: "textures".to_string(),
+            value: "e30=".to_string(),
+            signature: None,
+        }]),
+        strict_error_handling: false,
+    };
+
+    let packet = success.as_uncompressed().unwrap();
+    assert_eq!(packet.packet_id, VarInt(0x02));
+
+    let mut expected = 0x0123456789abcdef0123456789abcdefu128
+        .to_be_bytes()
+        .to_vec();
+    expected.extend_from_slice(b"\x05Notch\x01\x08textures\x04e30=\x00\x00");
+    assert_eq!(packet.payload, expected);
+
+    assert_eq!(
+        packet
+            .convert::<login::clientbound::LoginSuccess>()
+            .unwrap(),
+        success
+    );
+}
+
+#[test]
+fn login_plugin_messages() {
+    let request = login::clientbound::LoginPluginRequest {
+        message_id: VarInt(3),
+        channel: "velocity:player_info".to_string(),
+        data: RestBuffer(vec![1, 2, 3]),
+    };
+    let packet = request.as_uncompressed().unwrap();
+    assert_eq!(
+        packet
+            .convert::<login::clientbound::LoginPluginRequest>()
+            .unwrap(),
+        request
+    );
+
+    let unknown = login::serverbound::LoginPluginResponse {
+        message_id: VarInt(3),
+        data: None,
+    };
+    let packet = unknown.as_uncompressed().unwrap();
+    assert_eq!(packet.payload, [3, 0]);
+    assert_eq!(
+        packet
+            .convert::<login::serverbound::LoginPluginResponse>()
+            .unwrap(),
+        unknown
+    );
+}
+
+#[test]
+fn known_packs_negotiation() {
+    let offer = configuration::clientbound::KnownPacks {
+        known_packs: PrefixedArray(vec![KnownPack::core("1.21")]),
+    };
+    let packet = offer.as_uncompressed().unwrap();
+    assert_eq!(packet.packet_id, VarInt(0x0E));
+    assert_eq!(packet.payload, b"\x01\x09minecraft\x04core\x041.21");
+
+    let reply = packet
+        .convert::<configuration::serverbound::KnownPacks>()
+        .unwrap();
+    assert_eq!(reply.known_packs, offer.known_packs);
+    assert_eq!(reply.as_uncompressed().unwrap().packet_id, VarInt(0x07));
+}
+
+#[test]
+fn registry_data_with_nbt() {
+    // {"height": 256, "effects": ["a"]} as nameless network NBT
+    let mut compound = vec![0x0A];
+    compound.extend_from_slice(b"\x03\x00\x06height\x00\x00\x01\x00");
+    compound.extend_from_slice(b"\x09\x00\x07effects\x08\x00\x00\x00\x01\x00\x01a");
+    compound.push(0x00);
+
+    let registry = configuration::clientbound::RegistryData {
+        registry_id: "minecraft:dimension_type".to_string(),
+        entries: PrefixedArray(vec![
+            configuration::clientbound::RegistryEntry {
+                entry_id: "minecraft:overworld".to_string(),
+                data: Some(Nbt(compound)),
+            },
+            configuration::clientbound::RegistryEntry {
+                entry_id: "minecraft:the_end".to_string(),
+                data: None,
+            },
+        ]),
+    };
+
+    let packet = registry.as_uncompressed().unwrap();
+    assert_eq!(
+        packet
+            .convert::<configuration::clientbound::RegistryData>()
+            .unwrap(),
+        registry
+    );
+}
+
+#[test]
+fn nbt_stops_at_end_of_tag() {
+    let mut cursor = Cursor::new(b"\x08\x00\x02hi\xFF".to_vec());
+    assert_eq!(
+        Nbt::deserialize(&mut cursor).unwrap(),
+        Nbt::string("hi").unwrap()
+    );
+    assert_eq!(cursor.position(), 5);
+
+    let mut cursor = Cursor::new(vec![0x00]);
+    assert!(Nbt::deserialize(&mut cursor).unwrap().is_empty());
+
+    let mut cursor = Cursor::new(vec![0x0D]);
+    assert!(Nbt::deserialize(&mut cursor).is_err());
+}
+
+#[test]
+fn nbt_string_length_limit() {
+    let longest = "a".repeat(u16::MAX as usize);
+    let nbt = Nbt::string(&longest).unwrap();
+    assert_eq!(nbt.0[1..3], [0xFF, 0xFF]);
+
+    assert!(matches!(
+        Nbt::string(&"a".repeat(u16::MAX as usize + 1)),
+        Err(SerializationError::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn bogus_string_lengths() {
+    use minecraft_protocol::{packet::UncompressedPacket, varint::VarIntError};
+
+    let negative = UncompressedPacket {
+        packet_id: VarInt(0x00),
+        payload: vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F],
+    };
+    assert!(matches!(
+        negative.convert::<login::serverbound::LoginStart>(),
+        Err(SerializationError::VarIntError(VarIntError::NegativeValue))
+    ));
+
+    let oversized = UncompressedPacket {
+        packet_id: VarInt(0x00),
+        payload: vec![0xFF, 0xFF, 0xFF, 0xFF, 0x07, b'a'],
+    };
+    assert!(matches!(
+        oversized.convert::<login::serverbound::LoginStart>(),
+        Err(SerializationError::IOError(_))
+    ));
+
+    let mut cursor = Cursor::new(vec![0x03, 0x01, 0x02]);
+    assert!(Vec::<u8>::deserialize(&mut cursor).is_err());
+}
+
+#[test]
+fn server_links() {
+    use configuration::clientbound::{ServerLink, ServerLinkLabel, ServerLinks};
+
+    let links = ServerLinks {
+        links: PrefixedArray(vec![
+            ServerLink {
+                label: ServerLinkLabel::BuiltIn(VarInt(6)),
+                url: "https://example.com".to_string(),
+            },
+            ServerLink {
+                label: ServerLinkLabel::Custom(Nbt::string("Map").unwrap()),
+                url: "https://map.example.com".to_string(),
+            },
+        ]),
+    };
+
+    let packet = links.as_uncompressed().unwrap();
+    assert_eq!(packet.packet_id, VarInt(0x10));
+    assert_eq!(packet.convert::<ServerLinks>().unwrap(), links);
+}
@@ -0,0 +1,399 @@
+use minecraft_protocol::{
+    nbt::Nbt,
+    packet::UncompressedPacket,
+    packets::{
+        login::clientbound::Property,
+        play::{
+            ClientboundPlayPacket, IdOr, MessageSignature, Position, ServerboundPlayPacket, Vec3,
+            clientbound::{
+                AddPlayer, BossBar, BossBarAction, ChatType, ChatTypeDecoration, DamageEvent,
+                DisguisedChatMessage, FilterMask, KeepAlive, Login, NumberFormat, Objective,
+                ObjectiveAction, PlayerChatMessage, PlayerInfoActions, PlayerInfoEntry,
+                PlayerInfoUpdate, SpawnInfo, StopSound, StopSoundFilter, SystemChatMessage,
+                TeamAction, TeamInfo, UpdateObjectives, UpdateScore, UpdateTeams,
+            },
+            serverbound::{Interact, InteractAction, SetPlayerPosition},
+        },
+    },
+    ser::{PrefixedArray, SerializationError},
+    varint::VarInt,
+};
+
+#[test]
+fn packet_ids_are_contiguous() {
+    for (index, (id, _)) in ClientboundPlayPacket::PACKETS.iter().enumerate() {
+        assert_eq!(*id, index as i32);
+    }
+    for (index, (id, _)) in ServerboundPlayPacket::PACKETS.iter().enumerate() {
+        assert_eq!(*id, index as i32);
+    }
+
+    assert_eq!(ClientboundPlayPacket::PACKETS.len(), 0x7C);
+    assert_eq!(ServerboundPlayPacket::PACKETS.len(), 0x3A);
+}
+
+#[test]
+fn position_packing() {
+    let position = Position::new(-1, 319, 33_554_431);
+    assert_eq!(Position::from_packed(position.to_packed()), position);
+
+    // x = 18357644, y = 831, z = -20882616 from the protocol documentation
+    let packed = 0b0100_0110_0000_0111_0110_0011_0010_1100_0001_0101_1011_0100_1000_0011_0011_1111;
+    assert_eq!(
+        Position::from_packed(packed),
+        Position::new(18357644, 831, -20882616)
+    );
+    assert_eq!(Position::new(18357644, 831, -20882616).to_packed(), packed);
+}
+
+#[test]
+fn decode_by_id() {
+    let packet = KeepAlive { keep_alive_id: 42 }.as_uncompressed().unwrap();
+    let decoded = ClientboundPlayPacket::decode(packet.clone()).unwrap();
+
+    assert_eq!(
+        decoded,
+        ClientboundPlayPacket::KeepAlive(KeepAlive { keep_alive_id: 42 })
+    );
+    assert_eq!(decoded.name(), "KeepAlive");
+    assert_eq!(decoded.packet_id(), VarInt(0x26));
+    assert_eq!(decoded.as_uncompressed().unwrap(), packet);
+}
+
+#[test]
+fn unknown_id_falls_through() {
+    let packet = UncompressedPacket {
+        packet_id: VarInt(0x7F),
+        payload: vec![1, 2, 3],
+    };
+
+    let decoded = ServerboundPlayPacket::decode(packet.clone()).unwrap();
+    assert_eq!(decoded, ServerboundPlayPacket::Unknown(packet.clone()));
+    assert_eq!(decoded.name(), "Unknown");
+    assert_eq!(decoded.as_uncompressed().unwrap(), packet);
+}
+
+#[test]
+fn trailing_bytes_are_rejected() {
+    let mut packet = KeepAlive { keep_alive_id: 1 }.as_uncompressed().unwrap();
+    packet.payload.push(0);
+
+    assert!(matches!(
+        ClientboundPlayPacket::decode(packet),
+        Err(SerializationError::TrailingBytes(1))
+    ));
+}
+
+#[test]
+fn login_round_trip() {
+    let login = Login {
+        entity_id: 7,
+        is_hardcore: false,
+        dimension_names: PrefixedArray(vec!["minecraft:overworld".to_string()]),
+        max_players: VarInt(20),
+        view_distance: VarInt(10),
+        simulation_distance: VarInt(8),
+        reduced_debug_info: false,
+        enable_respawn_screen: true,
+        do_limited_crafting: false,
+        spawn_info: SpawnInfo {
+            dimension_type: VarInt(0),
+            dimension_name: "minecraft:overworld".to_string(),
+            hashed_seed: -5,
+            game_mode: 1,
+            previous_game_mode: -1,
+            is_debug: false,
+            is_flat: true,
+            death_location: None,
+            portal_cooldown: VarInt(0),
+        },
+        enforces_secure_chat: true,
+    };
+
+    let decoded = ClientboundPlayPacket::decode(login.as_uncompressed().unwrap()).unwrap();
+    assert_eq!(decoded, ClientboundPlayPacket::Login(login));
+}
+
+#[test]
+fn optional_fields_round_trip() {
+    let event = DamageEvent {
+        entity_id: VarInt(3),
+        source_type_id: VarInt(1),
+        source_cause_id: VarInt(0),
+        source_direct_id: VarInt(0),
+        source_position: Some(Vec3 {
+            x: 1.0,
+            y: 64.0,
+            z: -1.5,
+        }),
+    };
+    let packet = event.as_uncompressed().unwrap();
+    assert_eq!(packet.convert_exact::<DamageEvent>().unwrap(), event);
+
+    let message = SystemChatMessage {
+        content: Nbt::string("hello").unwrap(),
+        overlay: false,
+    };
+    let packet = message.as_uncompressed().unwrap();
+    assert_eq!(
+        packet.convert_exact::<SystemChatMessage>().unwrap(),
+        message
+    );
+}
+
+#[test]
+fn player_info_update_actions() {
+    let update = PlayerInfoUpdate {
+        update: PlayerInfoActions {
+            actions: PlayerInfoUpdate::ADD_PLAYER | PlayerInfoUpdate::UPDATE_LATENCY,
+            players: vec![PlayerInfoEntry {
+                uuid: 1,
+                add_player: Some(AddPlayer {
+                    name: "Notch".to_string(),
+                    properties: PrefixedArray(vec![Property {
+                        name: "textures".to_string(),
+                        value: "e30=".to_string(),
+                        signature: None,
+                    }]),
+                }),
+                latency: Some(VarInt(25)),
+                ..Default::default()
+            }],
+        },
+    };
+
+    let packet = update.as_uncompressed().unwrap();
+    assert_eq!(packet.payload[0], 0x11);
+    assert_eq!(packet.convert_exact::<PlayerInfoUpdate>().unwrap(), update);
+
+    let missing = PlayerInfoUpdate {
+        update: PlayerInfoActions {
+            actions: PlayerInfoUpdate::UPDATE_LISTED,
+            players: vec![PlayerInfoEntry::default()],
+        },
+    };
+    assert!(missing.as_uncompressed().is_err());
+}
+
+#[test]
+fn boss_bar_actions() {
+    for action in [
+        BossBarAction::Add {
+            title: Nbt::string("Ender Dragon").unwrap(),
+            health: 1.0,
+            color: VarInt(5),
+            division: VarInt(0),
+            flags: 0x03,
+        },
+        BossBarAction::Remove,
+        BossBarAction::UpdateHealth(0.5),
+        BossBarAction::UpdateTitle(Nbt::string("Wither").unwrap()),
+        BossBarAction::UpdateStyle {
+            color: VarInt(2),
+            division: VarInt(4),
+        },
+        BossBarAction::UpdateFlags(0x04),
+    ] {
+        let boss_bar = BossBar { uuid: 7, action };
+        let decoded = ClientboundPlayPacket::decode(boss_bar.as_uncompressed().unwrap()).unwrap();
+        assert_eq!(decoded, ClientboundPlayPacket::BossBar(boss_bar));
+    }
+
+    let mut invalid = BossBar {
+        uuid: 7,
+        action: BossBarAction::Remove,
+    }
+    .as_uncompressed()
+    .unwrap();
+    invalid.payload[16] = 6;
+    assert!(matches!(
+        ClientboundPlayPacket::decode(invalid),
+        Err(SerializationError::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn chat_messages() {
+    let chat_type = ChatType {
+        chat: ChatTypeDecoration {
+            translation_key: "chat.type.text".to_string(),
+            parameters: PrefixedArray(vec![VarInt(0), VarInt(2)]),
+            style: Nbt::empty(),
+        },
+        narration: ChatTypeDecoration {
+            translation_key: "chat.type.text.narrate".to_string(),
+            parameters: PrefixedArray(vec![VarInt(0), VarInt(2)]),
+            style: Nbt::empty(),
+        },
+    };
+
+    let disguised = DisguisedChatMessage {
+        message: Nbt::string("hello").unwrap(),
+        chat_type: IdOr::Inline(chat_type),
+        sender_name: Nbt::string("Server").unwrap(),
+        target_name: None,
+    };
+    let decoded = ClientboundPlayPacket::decode(disguised.as_uncompressed().unwrap()).unwrap();
+    assert_eq!(
+        decoded,
+        ClientboundPlayPacket::DisguisedChatMessage(disguised)
+    );
+
+    for filter in [
+        FilterMask::PassThrough,
+        FilterMask::FullyFiltered,
+        FilterMask::PartiallyFiltered(PrefixedArray(vec![0b1010])),
+    ] {
+        let message = PlayerChatMessage {
+            sender: 1,
+            index: VarInt(0),
+            message_signature: Some(Box::new([7; 256])),
+            message: "hello".to_string(),
+            timestamp: 1_700_000_000_000,
+            salt: 42,
+            previous_messages: PrefixedArray(vec![MessageSignature::Id(3)]),
+            unsigned_content: None,
+            filter,
+            chat_type: IdOr::Id(0),
+            sender_name: Nbt::string("Notch").unwrap(),
+            target_name: Some(Nbt::string("jeb_").unwrap()),
+        };
+        let decoded = ClientboundPlayPacket::decode(message.as_uncompressed().unwrap()).unwrap();
+        assert_eq!(decoded, ClientboundPlayPacket::PlayerChatMessage(message));
+    }
+}
+
+#[test]
+fn scoreboard_packets() {
+    let objective = Objective {
+        display_name: Nbt::string("Kills").unwrap(),
+        render_type: VarInt(0),
+        number_format: Some(NumberFormat::Styled(Nbt::empty())),
+    };
+    for action in [
+        ObjectiveAction::Create(objective.clone()),
+        ObjectiveAction::Remove,
+        ObjectiveAction::Update(objective),
+    ] {
+        let objectives = UpdateObjectives {
+            objective_name: "kills".to_string(),
+            action,
+        };
+        let packet = objectives.as_uncompressed().unwrap();
+        assert_eq!(
+            packet.convert_exact::<UpdateObjectives>().unwrap(),
+            objectives
+        );
+    }
+
+    let info = TeamInfo {
+        display_name: Nbt::string("Red").unwrap(),
+        friendly_flags: 0x01,
+        name_tag_visibility: "always".to_string(),
+        collision_rule: "pushOtherTeams".to_string(),
+        color: VarInt(12),
+        prefix: Nbt::string("[R] ").unwrap(),
+        suffix: Nbt::empty(),
+    };
+    let players = PrefixedArray(vec!["Notch".to_string(), "jeb_".to_string()]);
+    for action in [
+        TeamAction::Create {
+            info: info.clone(),
+            entities: players.clone(),
+        },
+        TeamAction::Remove,
+        TeamAction::Update(info),
+        TeamAction::AddEntities(players.clone()),
+        TeamAction::RemoveEntities(players),
+    ] {
+        let teams = UpdateTeams {
+            team_name: "red".to_string(),
+            action,
+        };
+        let packet = teams.as_uncompressed().unwrap();
+        assert_eq!(packet.convert_exact::<UpdateTeams>().unwrap(), teams);
+    }
+
+    for number_format in [
+        None,
+        Some(NumberFormat::Blank),
+        Some(NumberFormat::Fixed(Nbt::string("-").unwrap())),
+    ] {
+        let score = UpdateScore {
+            entity_name: "Notch".to_string(),
+            objective_name: "kills".to_string(),
+            value: VarInt(3),
+            display_name: None,
+            number_format,
+        };
+        let packet = score.as_uncompressed().unwrap();
+        assert_eq!(packet.convert_exact::<UpdateScore>().unwrap(), score);
+    }
+}
+
+#[test]
+fn stop_sound_flags() {
+    let sound = StopSound {
+        filter: StopSoundFilter {
+            source: None,
+            sound: Some("minecraft:music_disc.cat".to_string()),
+        },
+    };
+    let packet = sound.as_uncompressed().unwrap();
+    assert_eq!(packet.payload[0], 0x02);
+    assert_eq!(packet.convert_exact::<StopSound>().unwrap(), sound);
+
+    let all = StopSound {
+        filter: StopSoundFilter {
+            source: Some(VarInt(2)),
+            sound: None,
+        },
+    };
+    let packet = all.as_uncompressed().unwrap();
+    assert_eq!(packet.payload, [0x01, 2]);
+    assert_eq!(packet.convert_exact::<StopSound>().unwrap(), all);
+}
+
+#[test]
+fn interact_actions() {
+    for action in [
+        InteractAction::Interact { hand: VarInt(0) },
+        InteractAction::Attack,
+        InteractAction::InteractAt {
+            target_x: 0.5,
+            target_y: 1.0,
+            target_z: 0.25,
+            hand: VarInt(1),
+        },
+    ] {
+        let interact = Interact {
+            entity_id: VarInt(12),
+            action,
+            sneaking: true,
+        };
+        let decoded = ServerboundPlayPacket::decode(interact.as_uncompressed().unwrap()).unwrap();
+        assert_eq!(decoded, ServerboundPlayPacket::Interact(interact));
+    }
+
+    let invalid = UncompressedPacket {
+        packet_id: Interact::PACKET_ID,
+        payload: vec![12, 5, 0],
+    };
+    assert!(matches!(
+        ServerboundPlayPacket::decode(invalid),
+        Err(SerializationError::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn from_packet() {
+    let packet: ServerboundPlayPacket = SetPlayerPosition {
+        x: 0.0,
+        feet_y: 64.0,
+        z: 0.0,
+        on_ground: true,
+    }
+    .into();
+    assert_eq!(packet.name(), "SetPlayerPosition");
+    assert_eq!(packet.packet_id(), VarInt(0x1A));
+}
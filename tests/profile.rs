@@ -0,0 +1,114 @@
+use std::io::Cursor;
+
+use minecraft_protocol::{
+    profile::{GameProfile, ProfileProperty, SkinModel, offline_uuid, uuid_from_hex},
+    ser::{Deserialize, Serialize},
+};
+
+// Signed with a throwaway 1024-bit key, `openssl dgst -sha1 -sign`
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+const PUBLIC_KEY: &str = "MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQCp/G95HzlBkwLh9Es9oZeG50Say8hYSNiQmvQup2dagqoYqUUYQp7Wd6pN9lrQN5Q9E953FT2vKgpk71RU9+OsC9ql/pC5ZFGVdDgb0D3k56zZceGD9s8TPsmkKP/x7iIWj0xoBAWkcm07C+fXdTgtGpF/Q2lCKzxtaVYXp4zOsQIDAQAB";
+const TEXTURES: &str = "eyJ0aW1lc3RhbXAiOjE3MDAwMDAwMDAwMDAsInByb2ZpbGVJZCI6IjA2OWE3OWY0NDRlOTQ3MjZhNWJlZmNhOTBlMzhhYWY1IiwicHJvZmlsZU5hbWUiOiJOb3RjaCIsInNpZ25hdHVyZVJlcXVpcmVkIjp0cnVlLCJ0ZXh0dXJlcyI6eyJTS0lOIjp7InVybCI6Imh0dHA6Ly90ZXh0dXJlcy5taW5lY3JhZnQubmV0L3RleHR1cmUvc2tpbiIsIm1ldGFkYXRhIjp7Im1vZGVsIjoic2xpbSJ9fSwiQ0FQRSI6eyJ1cmwiOiJodHRwOi8vdGV4dHVyZXMubWluZWNyYWZ0Lm5ldC90ZXh0dXJlL2NhcGUifX19";
+const SIGNATURE: &str = "W+lENUhSGSdkz6IM14Onj2rpj5hKOOKqFJAZO+k5wDnzbxLC3IudoT/iRkIFS1t3BxDuIvb6xVabLEkXYIhup/VpgW/oEbpiBt1Kiw220q47/0h2ScCU/ufddKkN9vkNM5PWFcM3TlQ3//wVtjuiRR2zVftcRKJq1d5VkjKYbVE=";
+
+fn notch() -> GameProfile {
+    let mut profile = GameProfile::new(0x069a79f444e94726a5befca90e38aaf5, "Notch");
+    profile.properties.push(ProfileProperty {
+        name: "textures".to_string(),
+        value: TEXTURES.to_string(),
+        signature: Some(SIGNATURE.to_string()),
+    });
+    profile
+}
+
+#[test]
+fn wire_roundtrip() {
+    let profile = notch();
+    let mut buf = Vec::new();
+    profile.serialize(&mut buf).unwrap();
+
+    // Same layout as the UUID, username and properties of Login Success
+    let mut login_success = Vec::new();
+    let packet = profile.login_success();
+    packet.uuid.serialize(&mut login_success).unwrap();
+    packet.username.serialize(&mut login_success).unwrap();
+    packet.properties.serialize(&mut login_success).unwrap();
+    assert_eq!(buf, login_success);
+
+    assert_eq!(
+        GameProfile::deserialize(&mut Cursor::new(&buf)).unwrap(),
+        profile
+    );
+    assert_eq!(GameProfile::from(&packet), profile);
+}
+
+#[test]
+fn decodes_textures() {
+    let textures = notch().textures().unwrap().unwrap();
+    assert_eq!(textures.profile_id, 0x069a79f444e94726a5befca90e38aaf5);
+    assert_eq!(textures.profile_name, "Notch");
+    assert!(textures.signature_required);
+    assert_eq!(
+        textures.skin_url(),
+        Some("http://textures.minecraft.net/texture/skin")
+    );
+    assert_eq!(textures.skin_model(), SkinModel::Slim);
+    assert_eq!(
+        textures.cape_url(),
+        Some("http://textures.minecraft.net/texture/cape")
+    );
+
+    assert_eq!(GameProfile::new(1, "Steve").textures().unwrap(), None);
+}
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+#[test]
+fn verifies_signatures() {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use minecraft_protocol::profile::ProfileError;
+
+    let key = STANDARD.decode(PUBLIC_KEY).unwrap();
+    let profile = notch();
+    profile.verify_signatures(&key).unwrap();
+
+    let mut tampered = profile.clone();
+    tampered.properties[0].value.insert(0, 'e');
+    assert!(matches!(
+        tampered.verify_signatures(&key),
+        Err(ProfileError::InvalidSignature(name)) if name == "textures"
+    ));
+
+    let mut unsigned = profile;
+    unsigned.properties[0].signature = None;
+    assert!(matches!(
+        unsigned.verify_signatures(&key),
+        Err(ProfileError::MissingSignature(_))
+    ));
+}
+
+#[test]
+fn parses_session_server_json() {
+    let profile: GameProfile = serde_json::from_str(
+        r#"{"id":"069a79f4-44e9-4726-a5be-fca90e38aaf5","name":"Notch","properties":[]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        profile,
+        GameProfile::new(0x069a79f444e94726a5befca90e38aaf5, "Notch")
+    );
+
+    let json = serde_json::to_value(&profile).unwrap();
+    assert_eq!(json["id"], "069a79f444e94726a5befca90e38aaf5");
+}
+
+#[test]
+fn offline_uuids() {
+    assert_eq!(
+        Some(offline_uuid("Notch")),
+        uuid_from_hex("b50ad385-829d-3141-a216-7e7d7539ba7f")
+    );
+    assert_eq!(
+        GameProfile::offline("jeb_").id,
+        uuid_from_hex("a762f560-4fce-3236-812a-b80efff0b62b").unwrap()
+    );
+}
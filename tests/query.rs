@@ -0,0 +1,143 @@
+#![cfg(feature = "tokio")]
+
+use minecraft_protocol::query::{FullStat, QueryClient, QueryError, QueryServer};
+use tokio::net::UdpSocket;
+
+fn stat() -> FullStat {
+    FullStat {
+        hostname: "A Minecraft Server".to_string(),
+        version: "1.21".to_string(),
+        online_players: 2,
+        max_players: 20,
+        host_port: 25565,
+        host_ip: "127.0.0.1".to_string(),
+        extra: vec![("whitelist".to_string(), "off".to_string())],
+        players: vec!["Notch".to_string(), "jeb_".to_string()],
+        ..Default::default()
+    }
+}
+
+/// Answers `requests` datagrams in the background.
+async fn spawn_server(requests: usize) -> (std::net::SocketAddr, tokio::task::JoinHandle<()>) {
+    let mut server = QueryServer::bind("127.0.0.1:0", stat()).await.unwrap();
+    let address = server.local_addr().unwrap();
+    let handle = tokio::spawn(async move {
+        for _ in 0..requests {
+            server.handle_next().await.unwrap();
+        }
+    });
+    (address, handle)
+}
+
+#[tokio::test]
+async fn basic_stat_over_loopback() {
+    let (address, server) = spawn_server(2).await;
+    let client = QueryClient::connect(address).await.unwrap();
+    assert_eq!(client.session_id() & !0x0F0F_0F0F, 0);
+
+    let basic = client.basic_stat().await.unwrap();
+    assert_eq!(basic.motd, "A Minecraft Server");
+    assert_eq!(basic.game_type, "SMP");
+    assert_eq!(basic.map, "world");
+    assert_eq!((basic.online_players, basic.max_players), (2, 20));
+    assert_eq!(basic.host_port, 25565);
+    assert_eq!(basic.host_ip, "127.0.0.1");
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn full_stat_over_loopback() {
+    let (address, server) = spawn_server(2).await;
+    let client = QueryClient::connect(address).await.unwrap();
+
+    let full = client.full_stat().await.unwrap();
+    assert_eq!(full, stat());
+    assert_eq!(full.get("game_id").as_deref(), Some("MINECRAFT"));
+    assert_eq!(full.get("numplayers").as_deref(), Some("2"));
+    assert_eq!(full.get("whitelist").as_deref(), Some("off"));
+    assert_eq!(full.get("missing"), None);
+    server.await.unwrap();
+}
+
+/// Full stat response of a vanilla server, as documented for the protocol.
+#[tokio::test]
+async fn parses_vanilla_full_stat() {
+    let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let client = QueryClient::connect(server.local_addr().unwrap())
+        .await
+        .unwrap();
+    let session = client.session_id().to_be_bytes();
+
+    let responder = tokio::spawn(async move {
+        let mut buf = [0; 64];
+        let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+        assert_eq!(buf[..3], [0xFE, 0xFD, 0x09]);
+        assert_eq!(len, 7);
+        let mut response = vec![0x09];
+        response.extend_from_slice(&session);
+        response.extend_from_slice(b"9513307\0");
+        server.send_to(&response, peer).await.unwrap();
+
+        let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+        assert_eq!(
+            buf[..len],
+            {
+                let mut request = vec![0xFE, 0xFD, 0x00];
+                request.extend_from_slice(&session);
+                request.extend_from_slice(&9_513_307i32.to_be_bytes());
+                request.extend_from_slice(&[0; 4]);
+                request
+            }[..]
+        );
+
+        let mut response = vec![0x00];
+        response.extend_from_slice(&session);
+        response.extend_from_slice(b"splitnum\0\x80\0");
+        response.extend_from_slice(
+            b"hostname\0A Minecraft Server\0gametype\0SMP\0game_id\0MINECRAFT\0\
+              version\x001.2.5\0plugins\0\0map\0world\0numplayers\x002\0\
+              maxplayers\x0020\0hostport\x0025565\0hostip\x00127.0.0.1\0\0",
+        );
+        response.extend_from_slice(b"\x01player_\0\0barneygale\0Vivalahelvig\0\0");
+        server.send_to(&response, peer).await.unwrap();
+    });
+
+    let full = client.full_stat().await.unwrap();
+    assert_eq!(full.version, "1.2.5");
+    assert_eq!(full.plugins, "");
+    assert_eq!(full.players, ["barneygale", "Vivalahelvig"]);
+    assert!(full.extra.is_empty());
+    responder.await.unwrap();
+}
+
+#[tokio::test]
+async fn server_rejects_unknown_token() {
+    let mut server = QueryServer::bind("127.0.0.1:0", stat()).await.unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    socket.connect(server.local_addr().unwrap()).await.unwrap();
+
+    socket
+        .send(&[0xFE, 0xFD, 0x00, 0, 0, 0, 1, 0, 0, 0, 42])
+        .await
+        .unwrap();
+    assert!(matches!(
+        server.handle_next().await,
+        Err(QueryError::InvalidPacket(_))
+    ));
+
+    socket.send(&[0x01, 0x02]).await.unwrap();
+    assert!(matches!(
+        server.handle_next().await,
+        Err(QueryError::InvalidPacket(_))
+    ));
+}
+
+#[tokio::test]
+async fn client_times_out() {
+    let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let client = QueryClient::connect(silent.local_addr().unwrap())
+        .await
+        .unwrap()
+        .with_timeout(std::time::Duration::from_millis(100));
+    assert!(matches!(client.handshake().await, Err(QueryError::Timeout)));
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "tokio")]
+
+use minecraft_protocol::rcon::{
+    MAX_COMMAND_LENGTH, RconClient, RconError, RconPacket, RconServer, RconSession,
+};
+use tokio::io::duplex;
+
+#[tokio::test]
+async fn packet_wire_format() {
+    let mut buf = Vec::new();
+    RconPacket::new(7, RconPacket::EXEC_COMMAND, "list")
+        .write(&mut buf)
+        .await
+        .unwrap();
+    assert_eq!(
+        buf,
+        [
+            14, 0, 0, 0, // length
+            7, 0, 0, 0, // request ID
+            2, 0, 0, 0, // type
+            b'l', b'i', b's', b't', 0, 0,
+        ]
+    );
+
+    let packet = RconPacket::read(&mut buf.as_slice()).await.unwrap();
+    assert_eq!(packet, RconPacket::new(7, RconPacket::EXEC_COMMAND, "list"));
+
+    assert!(matches!(
+        RconPacket::read(&mut [3, 0, 0, 0].as_slice()).await,
+        Err(RconError::InvalidPacket(_))
+    ));
+    assert!(matches!(
+        RconPacket::read(&mut [].as_slice()).await,
+        Err(RconError::Closed)
+    ));
+}
+
+#[tokio::test]
+async fn commands_over_loopback() {
+    let server = RconServer::bind("127.0.0.1:0", "hunter2").await.unwrap();
+    let address = server.local_addr().unwrap();
+
+    let long_output = "é".repeat(5000);
+    let expected = long_output.clone();
+    let handle = tokio::spawn(async move {
+        let (mut session, _) = server.accept().await.unwrap();
+        let mut commands = Vec::new();
+        while let Some(request) = session.next_command().await.unwrap() {
+            let output = match request.command.as_str() {
+                "long" => long_output.clone(),
+                command => format!("ran {command}"),
+            };
+            session.respond(&request, &output).await.unwrap();
+            commands.push(request.command);
+        }
+        commands
+    });
+
+    let mut client = RconClient::connect(address, "hunter2").await.unwrap();
+    assert_eq!(client.command("list").await.unwrap(), "ran list");
+    assert_eq!(client.command("long").await.unwrap(), expected);
+    assert_eq!(client.command("").await.unwrap(), "ran ");
+    drop(client);
+
+    assert_eq!(handle.await.unwrap(), ["list", "long", ""]);
+}
+
+#[tokio::test]
+async fn wrong_password() {
+    let server = RconServer::bind("127.0.0.1:0", "hunter2").await.unwrap();
+    let address = server.local_addr().unwrap();
+    let handle = tokio::spawn(async move { server.accept().await });
+
+    assert!(matches!(
+        RconClient::connect(address, "*******").await,
+        Err(RconError::AuthFailed)
+    ));
+    assert!(matches!(handle.await.unwrap(), Err(RconError::AuthFailed)));
+}
+
+/// Vanilla splits responses mid-character and answers the trailing empty
+/// packet with "Unknown request 0".
+#[tokio::test]
+async fn joins_vanilla_fragments() {
+    let (client_stream, mut server) = duplex(1 << 16);
+
+    let handle = tokio::spawn(async move {
+        let auth = RconPacket::read(&mut server).await.unwrap();
+        assert_eq!(auth.kind, RconPacket::AUTH);
+        // Source servers send an empty Response Value first
+        RconPacket::new(auth.request_id, RconPacket::RESPONSE_VALUE, "")
+            .write(&mut server)
+            .await
+            .unwrap();
+        RconPacket::new(auth.request_id, RconPacket::AUTH_RESPONSE, "")
+            .write(&mut server)
+            .await
+            .unwrap();
+
+        let command = RconPacket::read(&mut server).await.unwrap();
+        let sentinel = RconPacket::read(&mut server).await.unwrap();
+        let output = "ü".repeat(3000).into_bytes();
+        for chunk in output.chunks(4095) {
+            RconPacket::new(command.request_id, RconPacket::RESPONSE_VALUE, chunk)
+                .write(&mut server)
+                .await
+                .unwrap();
+        }
+        RconPacket::new(
+            sentinel.request_id,
+            RconPacket::RESPONSE_VALUE,
+            "Unknown request 0",
+        )
+        .write(&mut server)
+        .await
+        .unwrap();
+    });
+
+    let mut client = RconClient::authenticate(client_stream, "pw").await.unwrap();
+    assert_eq!(client.command("help").await.unwrap(), "ü".repeat(3000));
+    handle.await.unwrap();
+}
+
+#[tokio::test]
+async fn session_answers_unknown_types() {
+    let (mut client, server) = duplex(1024);
+
+    RconPacket::new(1, RconPacket::AUTH, "pw")
+        .write(&mut client)
+        .await
+        .unwrap();
+    let handle = tokio::spawn(async move {
+        let mut session = RconSession::authenticate(server, "pw").await.unwrap();
+        session.next_command().await.unwrap()
+    });
+    assert_eq!(
+        RconPacket::read(&mut client).await.unwrap(),
+        RconPacket::new(1, RconPacket::AUTH_RESPONSE, "")
+    );
+
+    RconPacket::new(2, RconPacket::RESPONSE_VALUE, "")
+        .write(&mut client)
+        .await
+        .unwrap();
+    assert_eq!(
+        RconPacket::read(&mut client).await.unwrap().body_text(),
+        "Unknown request 0"
+    );
+
+    drop(client);
+    assert_eq!(handle.await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn rejects_long_commands() {
+    let (client_stream, mut server) = duplex(1024);
+    tokio::spawn(async move {
+        let auth = RconPacket::read(&mut server).await.unwrap();
+        RconPacket::new(auth.request_id, RconPacket::AUTH_RESPONSE, "")
+            .write(&mut server)
+            .await
+            .unwrap();
+        server
+    });
+
+    let mut client = RconClient::authenticate(client_stream, "pw").await.unwrap();
+    let command = "x".repeat(MAX_COMMAND_LENGTH + 1);
+    assert!(matches!(
+        client.command(&command).await,
+        Err(RconError::CommandTooLong(_))
+    ));
+}
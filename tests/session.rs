@@ -0,0 +1,135 @@
+use std::{io, net::IpAddr, sync::Mutex};
+
+use futures::executor::block_on;
+use minecraft_protocol::{
+    profile::{GameProfile, ProfileProperty},
+    session::{HttpResponse, HttpTransport, SessionClient, SessionError},
+};
+
+/// Records requests and answers each with the same canned response.
+struct MockTransport {
+    response: HttpResponse,
+    requests: Mutex<Vec<(String, Option<String>)>>,
+}
+
+impl MockTransport {
+    fn new(status: u16, body: &str) -> Self {
+        Self {
+            response: HttpResponse {
+                status,
+                body: body.to_string(),
+            },
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn requests(&self) -> Vec<(String, Option<String>)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    async fn get(&self, url: &str) -> io::Result<HttpResponse> {
+        self.requests.lock().unwrap().push((url.to_string(), None));
+        Ok(self.response.clone())
+    }
+
+    async fn post_json(&self, url: &str, body: &str) -> io::Result<HttpResponse> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((url.to_string(), Some(body.to_string())));
+        Ok(self.response.clone())
+    }
+}
+
+fn client(status: u16, body: &str) -> SessionClient<MockTransport> {
+    SessionClient::new(MockTransport::new(status, body)).with_base_url("http://127.0.0.1:8080/")
+}
+
+const PROFILE: &str = r#"{
+    "id": "069a79f444e94726a5befca90e38aaf5",
+    "name": "Notch",
+    "properties": [{"name": "textures", "value": "e30=", "signature": "c2ln"}]
+}"#;
+
+#[test]
+fn join_posts_token_profile_and_hash() {
+    let session = client(204, "");
+    block_on(session.join(
+        "token",
+        0x069a79f444e94726a5befca90e38aaf5,
+        "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1",
+    ))
+    .unwrap();
+
+    let requests = session.transport().requests();
+    assert_eq!(
+        requests[0].0,
+        "http://127.0.0.1:8080/session/minecraft/join"
+    );
+    let body: serde_json::Value = serde_json::from_str(requests[0].1.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "accessToken": "token",
+            "selectedProfile": "069a79f444e94726a5befca90e38aaf5",
+            "serverId": "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1",
+        })
+    );
+}
+
+#[test]
+fn has_joined_returns_profile() {
+    let session = client(200, PROFILE);
+    let ip: IpAddr = "::1".parse().unwrap();
+    let profile = block_on(session.has_joined("Notch", "-1a2b", Some(ip))).unwrap();
+
+    assert_eq!(
+        profile,
+        GameProfile {
+            id: 0x069a79f444e94726a5befca90e38aaf5,
+            name: "Notch".to_string(),
+            properties: vec![ProfileProperty {
+                name: "textures".to_string(),
+                value: "e30=".to_string(),
+                signature: Some("c2ln".to_string()),
+            }],
+        }
+    );
+    assert_eq!(
+        session.transport().requests()[0].0,
+        "http://127.0.0.1:8080/session/minecraft/hasJoined?username=Notch&serverId=-1a2b&ip=%3A%3A1"
+    );
+}
+
+#[test]
+fn error_responses() {
+    assert!(matches!(
+        block_on(client(204, "").has_joined("Notch", "0", None)),
+        Err(SessionError::NotVerified)
+    ));
+    assert!(matches!(
+        block_on(client(429, "").has_joined("Notch", "0", None)),
+        Err(SessionError::RateLimited)
+    ));
+    assert!(matches!(
+        block_on(client(429, "").join("token", 0, "0")),
+        Err(SessionError::RateLimited)
+    ));
+
+    let forbidden = r#"{"error":"ForbiddenOperationException","errorMessage":"Invalid token."}"#;
+    match block_on(client(403, forbidden).join("token", 0, "0")) {
+        Err(SessionError::Forbidden(message)) => assert_eq!(message, "Invalid token."),
+        other => panic!("{other:?}"),
+    }
+
+    assert!(matches!(
+        block_on(client(500, "oops").has_joined("Notch", "0", None)),
+        Err(SessionError::UnexpectedStatus(500, _))
+    ));
+    assert!(matches!(
+        block_on(client(200, "{}").has_joined("Notch", "0", None)),
+        Err(SessionError::Json(_))
+    ));
+}
@@ -0,0 +1,468 @@
+use minecraft_protocol::status::{
+    FaviconError, StatusResponse,
+    favicon::{self, DATA_URI_PREFIX},
+};
+
+const VANILLA: &str = r#"{
+    "version": {"name": "1.21", "protocol": 767},
+    "players": {"max": 20, "online": 1, "sample": [{"name": "Notch", "id": "069a79f4-44e9-4726-a5be-fca90e38aaf5"}]},
+    "description": {"text": "A ", "extra": [{"text": "Minecraft", "bold": true}, " Server"]},
+    "favicon": "data:image/png;base64,AAAA",
+    "enforcesSecureChat": true
+}"#;
+
+#[test]
+fn parses_vanilla_status() {
+    let status = StatusResponse::from_json(VANILLA).unwrap();
+    assert_eq!(status.version.protocol, 767);
+    let players = status.players.as_ref().unwrap();
+    assert_eq!((players.online, players.max), (1, 20));
+    assert_eq!(players.sample[0].name, "Notch");
+    assert_eq!(status.description_text(), "A Minecraft Server");
+    assert_eq!(
+        status.favicon.as_deref(),
+        Some("data:image/png;base64,AAAA")
+    );
+    assert!(status.enforces_secure_chat);
+
+    assert_eq!(
+        StatusResponse::from_json(&status.to_json()).unwrap(),
+        status
+    );
+}
+
+#[test]
+fn parses_minimal_status() {
+    let status =
+        StatusResponse::from_json(r#"{"version":{"name":"x","protocol":5},"description":"Hi"}"#)
+            .unwrap();
+    assert_eq!(status.players, None);
+    assert_eq!(status.description_text(), "Hi");
+    assert!(!status.enforces_secure_chat);
+}
+
+/// PNG signature and IHDR chunk, enough for the header checks.
+fn png_header(width: u32, height: u32) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend_from_slice(&width.to_be_bytes());
+    png.extend_from_slice(&height.to_be_bytes());
+    png.extend_from_slice(&[8, 6, 0, 0, 0]);
+    png
+}
+
+#[test]
+fn favicon_round_trips() {
+    let png = png_header(64, 64);
+    let mut status = StatusResponse::from_json(r#"{"version":{"name":"x","protocol":5}}"#).unwrap();
+    assert_eq!(status.favicon_png(), Ok(None));
+
+    status.set_favicon_png(&png).unwrap();
+    assert!(
+        status
+            .favicon
+            .as_ref()
+            .unwrap()
+            .starts_with(DATA_URI_PREFIX)
+    );
+    assert_eq!(status.favicon_png(), Ok(Some(png.clone())));
+
+    // Some servers wrap the base64
+    let uri = status.favicon.unwrap();
+    let (head, tail) = uri.split_at(DATA_URI_PREFIX.len() + 8);
+    assert_eq!(favicon::decode(&format!("{head}\n{tail}")), Ok(png));
+}
+
+#[test]
+fn favicon_errors() {
+    assert_eq!(
+        favicon::validate(&png_header(128, 64)),
+        Err(FaviconError::InvalidSize {
+            width: 128,
+            height: 64
+        })
+    );
+    assert_eq!(favicon::validate(b"GIF89a"), Err(FaviconError::NotPng));
+    assert_eq!(
+        favicon::validate(&png_header(64, 64)[..20]),
+        Err(FaviconError::Truncated)
+    );
+    assert_eq!(favicon::validate(b"\x89PN"), Err(FaviconError::Truncated));
+
+    assert_eq!(
+        favicon::decode("data:image/jpeg;base64,AAAA"),
+        Err(FaviconError::NotDataUri)
+    );
+    assert!(matches!(
+        favicon::decode("data:image/png;base64,!!"),
+        Err(FaviconError::Base64(_))
+    ));
+    assert_eq!(
+        StatusResponse::from_json(VANILLA).unwrap().favicon_png(),
+        Err(FaviconError::NotPng)
+    );
+
+    let mut status = StatusResponse::from_json(VANILLA).unwrap();
+    assert!(status.set_favicon_png(&png_header(32, 32)).is_err());
+    assert_eq!(
+        status.favicon.as_deref(),
+        Some("data:image/png;base64,AAAA")
+    );
+}
+
+#[cfg(feature = "tokio")]
+mod ping {
+    use std::time::Duration;
+
+    use minecraft_protocol::{
+        connection::Connection,
+        packets::{
+            handshake::serverbound::Handshake,
+            status::{
+                clientbound::{PongResponse, StatusResponse},
+                serverbound::{PingRequest, StatusRequest},
+            },
+        },
+        status::{PingOptions, StatusError, ping},
+    };
+    use tokio::net::TcpListener;
+
+    use super::VANILLA;
+
+    async fn serve_status(listener: TcpListener) -> Handshake {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut connection = Connection::server(socket);
+
+        let handshake: Handshake = connection.recv().await.unwrap();
+        connection.recv::<StatusRequest>().await.unwrap();
+        connection
+            .send(&StatusResponse {
+                json_response: VANILLA.to_string(),
+            })
+            .await
+            .unwrap();
+
+        let ping: PingRequest = connection.recv().await.unwrap();
+        connection
+            .send(&PongResponse {
+                payload: ping.payload,
+            })
+            .await
+            .unwrap();
+        handshake
+    }
+
+    #[tokio::test]
+    async fn pings_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_status(listener));
+
+        let response = ping(&format!("127.0.0.1:{port}"), &PingOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(response.status.version.name, "1.21");
+        assert!(response.latency.is_some());
+
+        let handshake = server.await.unwrap();
+        assert_eq!(handshake.server_address, "127.0.0.1");
+        assert_eq!(handshake.server_port, port);
+        assert_eq!(handshake.next_state, Handshake::STATUS);
+    }
+
+    #[tokio::test]
+    async fn times_out_on_silent_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let options = PingOptions {
+            timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ping(&format!("127.0.0.1:{port}"), &options).await,
+            Err(StatusError::Timeout)
+        ));
+        drop(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_addresses() {
+        for address in ["", ":25565", "host:port", "[::1", "[::1]x"] {
+            assert!(
+                matches!(
+                    ping(address, &PingOptions::default()).await,
+                    Err(StatusError::InvalidAddress(_))
+                ),
+                "{address}"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod legacy_ping {
+    use std::time::Duration;
+
+    use minecraft_protocol::status::{
+        StatusError,
+        legacy::{
+            LegacyPing, LegacyStatus, is_legacy_ping, legacy_ping, legacy_ping_stream,
+            read_legacy_ping, write_legacy_response,
+        },
+    };
+    use tokio::{
+        io::AsyncWriteExt,
+        net::{TcpListener, TcpStream},
+    };
+
+    fn status() -> LegacyStatus {
+        LegacyStatus {
+            version: Some((78, "1.6.4".to_string())),
+            motd: "A Minecraft Server".to_string(),
+            online: 3,
+            max_players: 20,
+        }
+    }
+
+    async fn respond(listener: TcpListener) -> LegacyPing {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        assert!(is_legacy_ping(&socket).await.unwrap());
+        let ping = read_legacy_ping(&mut socket).await.unwrap();
+        write_legacy_response(&mut socket, &ping, &status())
+            .await
+            .unwrap();
+        ping
+    }
+
+    #[test]
+    fn kick_strings() {
+        assert_eq!(
+            status().to_kick_string(&LegacyPing::V1_4),
+            "§1\u{0}78\u{0}1.6.4\u{0}A Minecraft Server\u{0}3\u{0}20"
+        );
+        assert_eq!(
+            status().to_kick_string(&LegacyPing::Beta),
+            "A Minecraft Server§3§20"
+        );
+
+        let beta = LegacyStatus::from_kick_string("Hi § there§1§10").unwrap();
+        assert_eq!(beta.version, None);
+        assert_eq!(beta.motd, "Hi § there");
+        assert_eq!((beta.online, beta.max_players), (1, 10));
+
+        assert!(matches!(
+            LegacyStatus::from_kick_string("§1\u{0}78\u{0}1.6.4"),
+            Err(StatusError::InvalidLegacyPing(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn every_variant_round_trips() {
+        let requests = [
+            LegacyPing::Beta,
+            LegacyPing::V1_4,
+            LegacyPing::V1_6 {
+                protocol_version: 78,
+                host: "localhost".to_string(),
+                port: 25565,
+            },
+        ];
+        for request in requests {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let server = tokio::spawn(respond(listener));
+
+            let response = legacy_ping(
+                &format!("127.0.0.1:{port}"),
+                &request,
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+            assert_eq!(server.await.unwrap(), request);
+
+            let mut expected = status();
+            if request == LegacyPing::Beta {
+                expected.version = None;
+            }
+            assert_eq!(response, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn detects_modern_handshake() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let mut client = TcpStream::connect(address).await.unwrap();
+        // Length-prefixed Handshake
+        client.write_all(&[0x10, 0x00]).await.unwrap();
+        let (socket, _) = listener.accept().await.unwrap();
+        assert!(!is_legacy_ping(&socket).await.unwrap());
+
+        // Nothing was consumed
+        let mut first = [0; 2];
+        socket.peek(&mut first).await.unwrap();
+        assert_eq!(first, [0x10, 0x00]);
+    }
+
+    #[tokio::test]
+    async fn rejects_non_kick_reply() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+        server.write_all(&[0x00, 0x00]).await.unwrap();
+        assert!(matches!(
+            legacy_ping_stream(&mut client, &LegacyPing::V1_4).await,
+            Err(StatusError::InvalidLegacyPing(_))
+        ));
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod responder {
+    use minecraft_protocol::{
+        connection::Connection,
+        packets::{
+            handshake::serverbound::Handshake,
+            login::{clientbound::Disconnect, serverbound::LoginStart},
+            status::{clientbound::StatusResponse as StatusPacket, serverbound::StatusRequest},
+        },
+        status::{
+            PingOptions, Served, StatusResponder, StatusResponse,
+            legacy::{LegacyPing, legacy_ping_stream},
+            ping,
+        },
+        varint::VarInt,
+    };
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::VANILLA;
+
+    fn handshake(next_state: VarInt) -> Handshake {
+        Handshake {
+            protocol_version: VarInt(767),
+            server_address: "localhost".to_string(),
+            server_port: 25565,
+            next_state,
+        }
+    }
+
+    async fn spawn(responder: StatusResponder) -> (u16, tokio::task::JoinHandle<Served>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            responder.serve_tcp(socket).await.unwrap()
+        });
+        (port, server)
+    }
+
+    #[tokio::test]
+    async fn serves_status_and_pong() {
+        let mut status = StatusResponse::from_json(VANILLA).unwrap();
+        status.set_favicon_png(&super::png_header(64, 64)).unwrap();
+        let (port, server) = spawn(StatusResponder::new(status.clone())).await;
+
+        let response = ping(&format!("127.0.0.1:{port}"), &PingOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(response.status, status);
+        assert_eq!(
+            response.status.favicon_png().unwrap(),
+            Some(super::png_header(64, 64))
+        );
+        assert!(matches!(
+            server.await.unwrap(),
+            Served::Status { pinged: true, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn client_may_skip_ping() {
+        let status = StatusResponse::from_json(VANILLA).unwrap();
+        let (port, server) = spawn(StatusResponder::new(status)).await;
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let mut connection = Connection::client(stream);
+        connection
+            .send(&handshake(Handshake::STATUS))
+            .await
+            .unwrap();
+        connection.send(&StatusRequest).await.unwrap();
+        connection.recv::<StatusPacket>().await.unwrap();
+        drop(connection);
+
+        assert!(matches!(
+            server.await.unwrap(),
+            Served::Status { pinged: false, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn kicks_login_attempts() {
+        let status = StatusResponse::from_json(VANILLA).unwrap();
+        let responder = StatusResponder::new(status)
+            .with_login_kick(serde_json::json!({"text": "Maintenance", "color": "red"}));
+        let (port, server) = spawn(responder).await;
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let mut connection = Connection::client(stream);
+        connection.send(&handshake(Handshake::LOGIN)).await.unwrap();
+        connection
+            .send(&LoginStart {
+                name: "Notch".to_string(),
+                uuid: 0,
+            })
+            .await
+            .unwrap();
+        let disconnect: Disconnect = connection.recv().await.unwrap();
+        let reason: serde_json::Value = serde_json::from_str(&disconnect.reason).unwrap();
+        assert_eq!(reason["text"], "Maintenance");
+
+        assert_eq!(
+            server.await.unwrap(),
+            Served::Login {
+                handshake: handshake(Handshake::LOGIN),
+                kicked: true,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_bogus_address_lengths() {
+        use tokio::io::AsyncWriteExt;
+
+        // Handshake whose server_address length is VarInt(-1), then one claiming 2 GB
+        for frame in [
+            [0x07, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F],
+            [0x07, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x07],
+        ] {
+            let status = StatusResponse::from_json(VANILLA).unwrap();
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let server = tokio::spawn(async move {
+                let (socket, _) = listener.accept().await.unwrap();
+                StatusResponder::new(status).serve_tcp(socket).await
+            });
+
+            let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+            stream.write_all(&frame).await.unwrap();
+            assert!(server.await.unwrap().is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn answers_legacy_ping() {
+        let status = StatusResponse::from_json(VANILLA).unwrap();
+        let (port, server) = spawn(StatusResponder::new(status)).await;
+
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let legacy = legacy_ping_stream(&mut stream, &LegacyPing::V1_4)
+            .await
+            .unwrap();
+        assert_eq!(legacy.version, Some((767, "1.21".to_string())));
+        assert_eq!(legacy.motd, "A Minecraft Server");
+        assert_eq!((legacy.online, legacy.max_players), (1, 20));
+        assert_eq!(server.await.unwrap(), Served::Legacy(LegacyPing::V1_4));
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::Cursor;
+
+use minecraft_protocol::{
+    packet::{PacketError, RawPacket},
+    varint::VarIntError,
+};
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+const KEY: [u8; 16] = *b"0123456789abcdef";
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn raw_packet_matches_async() {
+    let packet = RawPacket {
+        data: vec![0x00, 1, 2, 3],
+    };
+
+    let mut sync_buf = Vec::new();
+    packet.write_sync(&mut sync_buf).unwrap();
+
+    let mut async_buf = Vec::new();
+    packet.write(&mut async_buf).await.unwrap();
+    assert_eq!(sync_buf, async_buf);
+    assert_eq!(sync_buf, [4, 0x00, 1, 2, 3]);
+
+    let read = RawPacket::read_sync(&mut Cursor::new(&sync_buf)).unwrap();
+    assert_eq!(read.data, packet.data);
+}
+
+#[test]
+fn raw_packet_write_flushes() {
+    let packet = RawPacket {
+        data: vec![0x00, 1, 2, 3],
+    };
+
+    let mut writer = std::io::BufWriter::new(Vec::new());
+    packet.write_sync(&mut writer).unwrap();
+    assert_eq!(writer.get_ref(), &[4, 0x00, 1, 2, 3]);
+}
+
+#[test]
+fn raw_packet_rejects_negative_length() {
+    let mut reader = Cursor::new([0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    assert!(matches!(
+        RawPacket::read_sync(&mut reader),
+        Err(PacketError::VarIntError(VarIntError::NegativeValue))
+    ));
+}
+
+#[cfg(all(feature = "tokio", any(feature = "openssl", feature = "rustcrypto")))]
+#[tokio::test]
+async fn cfb8_sync_and_async_agree() {
+    use minecraft_protocol::cfb8_stream::{CFB8ReadHalf, CFB8WriteHalf};
+    use std::io::{Read, Write};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+    let mut sync_writer = CFB8WriteHalf::new(Vec::new(), &KEY).unwrap();
+    Write::write_all(&mut sync_writer, &plaintext[..10]).unwrap();
+    Write::write_all(&mut sync_writer, &plaintext[10..]).unwrap();
+    let sync_ciphertext = sync_writer.into_inner();
+
+    let mut async_writer = CFB8WriteHalf::new(Vec::new(), &KEY).unwrap();
+    AsyncWriteExt::write_all(&mut async_writer, plaintext)
+        .await
+        .unwrap();
+    assert_eq!(sync_ciphertext, async_writer.into_inner());
+    assert_ne!(&sync_ciphertext[..], &plaintext[..]);
+
+    let mut sync_reader = CFB8ReadHalf::new(Cursor::new(sync_ciphertext.clone()), &KEY).unwrap();
+    let mut decrypted = Vec::new();
+    Read::read_to_end(&mut sync_reader, &mut decrypted).unwrap();
+    assert_eq!(decrypted, plaintext);
+
+    let mut async_reader = CFB8ReadHalf::new(Cursor::new(sync_ciphertext), &KEY).unwrap();
+    let mut decrypted = Vec::new();
+    AsyncReadExt::read_to_end(&mut async_reader, &mut decrypted)
+        .await
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[cfg(any(feature = "openssl", feature = "rustcrypto"))]
+#[test]
+fn cfb8_stream_over_std_tcp() {
+    use minecraft_protocol::cfb8_stream::CFB8Stream;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = std::thread::spawn(move || {
+        let (socket, _) = listener.accept().unwrap();
+        let mut stream = CFB8Stream::new_from_std_tcp(socket, &KEY).unwrap();
+        let packet = RawPacket::read_sync(&mut stream).unwrap();
+        packet.write_sync(&mut stream).unwrap();
+    });
+
+    let socket = std::net::TcpStream::connect(addr).unwrap();
+    let mut stream = CFB8Stream::new_from_std_tcp(socket, &KEY).unwrap();
+    let packet = RawPacket {
+        data: vec![0x26, 0, 0, 0, 0, 0, 0, 0, 42],
+    };
+    packet.write_sync(&mut stream).unwrap();
+
+    let echoed = RawPacket::read_sync(&mut stream).unwrap();
+    assert_eq!(echoed.data, packet.data);
+    server.join().unwrap();
+}
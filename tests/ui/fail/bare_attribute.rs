@@ -0,0 +1,7 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet]
+struct StatusRequest;
+
+fn main() {}
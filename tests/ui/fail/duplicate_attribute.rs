@@ -0,0 +1,8 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(0x00)]
+#[packet(0x01)]
+struct StatusRequest;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(0x00)]
+enum State {
+    Status,
+    Login,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(0x00)]
+struct KeepAlive {
+    #[packet(id = 0x01)]
+    id: i64,
+}
+
+fn main() {}
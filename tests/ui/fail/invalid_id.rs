@@ -0,0 +1,7 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(0x00, 0x01)]
+struct StatusRequest;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+struct Handshake {
+    server_port: u16,
+}
+
+fn main() {}
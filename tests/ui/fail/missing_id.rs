@@ -0,0 +1,9 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(since = 763)]
+struct KeepAlive {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(id = 0x1A, since = 759, until = 764)]
+#[packet(id = 0x1B, since = 764)]
+struct KeepAlive {
+    id: i64,
+}
+
+fn main() {}
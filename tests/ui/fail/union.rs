@@ -0,0 +1,10 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(0x00)]
+union Value {
+    int: i32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use minecraft_protocol::Packet;
+
+#[derive(Packet)]
+#[packet(id = 0x1A, after = 759)]
+struct KeepAlive {
+    id: i64,
+}
+
+fn main() {}
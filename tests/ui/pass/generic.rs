@@ -0,0 +1,29 @@
+use minecraft_protocol::{Packet, varint::VarInt};
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(0x02)]
+struct PluginMessage<T>
+where
+    T: Clone,
+{
+    channel: String,
+    data: T,
+}
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(0x04)]
+struct Wrapper<T>(T);
+
+fn main() {
+    let packet = PluginMessage {
+        channel: "minecraft:brand".to_string(),
+        data: VarInt(42),
+    };
+    let uncompressed = packet.as_uncompressed().unwrap();
+    assert_eq!(PluginMessage::<VarInt>::PACKET_ID, VarInt(0x02));
+    assert_eq!(uncompressed.convert::<PluginMessage<VarInt>>().unwrap(), packet);
+
+    let wrapper = Wrapper(7u8);
+    let uncompressed = wrapper.as_uncompressed().unwrap();
+    assert_eq!(uncompressed.convert::<Wrapper<u8>>().unwrap(), wrapper);
+}
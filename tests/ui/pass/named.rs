@@ -0,0 +1,23 @@
+use minecraft_protocol::{Packet, varint::VarInt};
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(0x00)]
+struct Handshake {
+    protocol_version: VarInt,
+    server_address: String,
+    server_port: u16,
+    next_state: VarInt,
+}
+
+fn main() {
+    let packet = Handshake {
+        protocol_version: VarInt(767),
+        server_address: "localhost".to_string(),
+        server_port: 25565,
+        next_state: VarInt(1),
+    };
+
+    let uncompressed = packet.as_uncompressed().unwrap();
+    assert_eq!(uncompressed.packet_id, Handshake::PACKET_ID);
+    assert_eq!(uncompressed.convert::<Handshake>().unwrap(), packet);
+}
@@ -0,0 +1,21 @@
+use minecraft_protocol::{Packet, varint::VarInt};
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(0x01)]
+struct PingRequest(i64);
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(0x03)]
+struct SetCompression(VarInt, bool);
+
+fn main() {
+    let ping = PingRequest(1234567890);
+    let uncompressed = ping.as_uncompressed().unwrap();
+    assert_eq!(uncompressed.packet_id, VarInt(0x01));
+    assert_eq!(uncompressed.payload, 1234567890i64.to_be_bytes());
+    assert_eq!(uncompressed.convert::<PingRequest>().unwrap(), ping);
+
+    let compression = SetCompression(VarInt(256), true);
+    let uncompressed = compression.as_uncompressed().unwrap();
+    assert_eq!(uncompressed.convert::<SetCompression>().unwrap(), compression);
+}
@@ -0,0 +1,23 @@
+use minecraft_protocol::{Packet, varint::VarInt};
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(0x00)]
+struct StatusRequest;
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(0x03)]
+struct LoginAcknowledged {}
+
+fn main() {
+    let uncompressed = StatusRequest.as_uncompressed().unwrap();
+    assert_eq!(uncompressed.packet_id, VarInt(0x00));
+    assert!(uncompressed.payload.is_empty());
+    assert_eq!(uncompressed.convert::<StatusRequest>().unwrap(), StatusRequest);
+
+    let uncompressed = LoginAcknowledged {}.as_uncompressed().unwrap();
+    assert!(uncompressed.payload.is_empty());
+    assert_eq!(
+        uncompressed.convert::<LoginAcknowledged>().unwrap(),
+        LoginAcknowledged {}
+    );
+}
@@ -0,0 +1,57 @@
+use minecraft_protocol::{Packet, ser::SerializationError, varint::VarInt};
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(id = 0x1A, since = 759, until = 762)]
+#[packet(id = 0x1B, since = 763, until = 765)]
+#[packet(id = 0x1D, since = 766)]
+struct ChatMessage {
+    message: String,
+    #[packet(until = 765)]
+    legacy_flag: bool,
+    #[packet(since = 766)]
+    checksum: u8,
+}
+
+#[derive(Packet, Debug, PartialEq)]
+#[packet(id = 0x05, since = 764)]
+struct ConfigurationOnly(#[packet(since = 767)] i32, VarInt);
+
+fn main() {
+    assert_eq!(ChatMessage::packet_id(758), None);
+    assert_eq!(ChatMessage::packet_id(759), Some(VarInt(0x1A)));
+    assert_eq!(ChatMessage::packet_id(764), Some(VarInt(0x1B)));
+    assert_eq!(ChatMessage::packet_id(767), Some(VarInt(0x1D)));
+
+    let old = ChatMessage {
+        message: "hi".to_string(),
+        legacy_flag: true,
+        checksum: 0,
+    };
+    let uncompressed = old.as_uncompressed_versioned(763).unwrap();
+    assert_eq!(uncompressed.packet_id, VarInt(0x1B));
+    assert_eq!(uncompressed.payload, [2, b'h', b'i', 1]);
+    let decoded = ChatMessage::deserialize_versioned(&mut &uncompressed.payload[..], 763).unwrap();
+    assert_eq!(decoded, old);
+
+    let new = ChatMessage {
+        message: "hi".to_string(),
+        legacy_flag: false,
+        checksum: 9,
+    };
+    let uncompressed = new.as_uncompressed_versioned(767).unwrap();
+    assert_eq!(uncompressed.packet_id, VarInt(0x1D));
+    assert_eq!(uncompressed.payload, [2, b'h', b'i', 9]);
+    let decoded = ChatMessage::deserialize_versioned(&mut &uncompressed.payload[..], 767).unwrap();
+    assert_eq!(decoded, new);
+
+    assert!(matches!(
+        old.as_uncompressed_versioned(758),
+        Err(SerializationError::UnsupportedVersion(758))
+    ));
+
+    let packet = ConfigurationOnly(5, VarInt(1));
+    let uncompressed = packet.as_uncompressed_versioned(766).unwrap();
+    assert_eq!(uncompressed.payload, [1]);
+    let decoded = ConfigurationOnly::deserialize_versioned(&mut &uncompressed.payload[..], 766).unwrap();
+    assert_eq!(decoded, ConfigurationOnly(0, VarInt(1)));
+}
@@ -0,0 +1,62 @@
+use minecraft_protocol::version::{ProtocolVersion, RELEASES, Release, SNAPSHOT_BIT};
+
+#[test]
+fn lookup_in_all_directions() {
+    assert_eq!(
+        ProtocolVersion::from_release_name("1.21.1"),
+        Some(ProtocolVersion(767))
+    );
+    assert_eq!(
+        ProtocolVersion::from_data_version(3953),
+        Some(ProtocolVersion::V1_21)
+    );
+    assert_eq!(
+        Release::latest_for(ProtocolVersion(767)).map(|r| r.name),
+        Some("1.21.1")
+    );
+    assert_eq!(
+        ProtocolVersion::V1_20_5
+            .releases()
+            .map(|r| r.name)
+            .collect::<Vec<_>>(),
+        ["1.20.5", "1.20.6"]
+    );
+    assert!(ProtocolVersion::from_release_name("1.0").is_none());
+    assert!(!ProtocolVersion(12345).is_known());
+}
+
+#[test]
+fn ordering_and_snapshots() {
+    let v = ProtocolVersion(767);
+    assert!(ProtocolVersion::V1_20_5 <= v);
+    assert!(v < ProtocolVersion::V1_21_2);
+    assert_eq!(ProtocolVersion::V1_21_1, ProtocolVersion::V1_21);
+
+    let snapshot = ProtocolVersion::snapshot(0xAB);
+    assert_eq!(snapshot.0, SNAPSHOT_BIT | 0xAB);
+    assert!(snapshot.is_snapshot());
+    assert_eq!(snapshot.snapshot_number(), Some(0xAB));
+    assert_eq!(ProtocolVersion::LATEST.snapshot_number(), None);
+    assert!(snapshot > ProtocolVersion::LATEST);
+}
+
+#[test]
+fn display() {
+    assert_eq!(ProtocolVersion(767).to_string(), "1.21-1.21.1 (767)");
+    assert_eq!(ProtocolVersion(764).to_string(), "1.20.2 (764)");
+    assert_eq!(
+        ProtocolVersion::snapshot(1).to_string(),
+        "snapshot 1 (1073741825)"
+    );
+    assert_eq!(ProtocolVersion(1).to_string(), "unknown (1)");
+}
+
+#[test]
+fn table_is_sorted() {
+    for pair in RELEASES.windows(2) {
+        assert!(pair[0].protocol <= pair[1].protocol);
+        if let (Some(a), Some(b)) = (pair[0].data_version, pair[1].data_version) {
+            assert!(a < b, "{} >= {}", pair[0].name, pair[1].name);
+        }
+    }
+}